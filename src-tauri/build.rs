@@ -1,8 +1,42 @@
 fn main() {
     #[cfg(not(target_os = "macos"))]
     {
-        std::fs::create_dir_all("resources").ok();
-        std::fs::write("resources/askpass", []).ok();
+        use std::{env, fs, path::Path, process::Command};
+        println!("cargo:rerun-if-changed=askpass/src");
+        println!("cargo:rerun-if-changed=askpass/Cargo.toml");
+        fs::create_dir_all("resources").ok();
+        let target = env::var("TARGET").unwrap();
+        let built = Command::new("cargo")
+            .args([
+                "build",
+                "--release",
+                "--target",
+                &target,
+                "--target-dir",
+                "target",
+                "--manifest-path",
+                "askpass/Cargo.toml",
+            ])
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+        let exe = if target.contains("windows") { "askpass.exe" } else { "askpass" };
+        let src = Path::new("target").join(&target).join("release").join(exe);
+        let failure = if !built {
+            Some("cargo build of askpass failed".to_string())
+        } else {
+            fs::copy(&src, "resources/askpass")
+                .err()
+                .map(|e| format!("could not copy {}: {}", src.display(), e))
+        };
+        if let Some(failure) = failure {
+            println!(
+                "cargo:warning=askpass helper not bundled ({}); SSH password auth will not work",
+                failure
+            );
+            // Keep the bundled resource path valid; password auth reports the missing helper
+            fs::write("resources/askpass", []).ok();
+        }
     }
     #[cfg(target_os = "macos")]
     {
@@ -1,25 +1,219 @@
 use anyhow::Result;
+use serde::Serialize;
 use serde_json;
 use std::process::Stdio;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 use tauri::{AppHandle, Emitter};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::process::{Child, Command, ChildStdin, ChildStdout};
-use tokio::sync::mpsc;
+use tokio::process::{Child, Command, ChildStderr, ChildStdin, ChildStdout};
+use tokio::sync::{mpsc, Mutex, Notify};
 use uuid::Uuid;
 
-use crate::config::{read_model_providers, read_profiles};
-use crate::protocol::{CodexConfig, Event, InputItem, Op, Submission};
+use crate::config::{read_config_table, read_model_providers, read_profiles};
+use crate::protocol::{
+    parse_event, CodexConfig, Event, EventMsg, InputItem, ModelProvider, Op, ReviewDecision,
+    SandboxPolicy, Submission,
+};
+use crate::services::codex::auto_approve_exec;
+use crate::services::ssh::{SshCredentials, SshProcess};
+use crate::services::usage::{project_key, record_usage, UsageRecord};
+use crate::state::PendingApprovals;
+use crate::utils::time::current_timestamp_millis;
 use crate::utils::codex_discovery::discover_codex_command;
 
 pub struct ProcessHandle {
     pub child: Child,
     pub stdin: ChildStdin,
     pub stdout: ChildStdout,
+    pub stderr: ChildStderr,
 }
 
 pub struct LocalProcess;
 
+// codex's own defaults, for settings neither the session nor config.toml sets
+const DEFAULT_MODEL: &str = "gpt-5";
+const DEFAULT_REASONING_EFFORT: &str = "medium";
+const DEFAULT_REASONING_SUMMARY: &str = "auto";
+
+/// Model to pass to codex: the provider profile's model when the provider has
+/// one, otherwise the session's own. None leaves it to config.toml.
+async fn resolve_model(config: &CodexConfig) -> Option<String> {
+    let mut model = config.model.clone();
+    if !config.provider.is_empty() && config.provider != "openai" {
+        if let (Ok(providers), Ok(profiles)) = (read_model_providers().await, read_profiles().await)
+        {
+            let has_provider = providers.contains_key(&config.provider)
+                || providers.contains_key(&config.provider.to_lowercase());
+            let profile = profiles
+                .get(&config.provider)
+                .or_else(|| profiles.get(&config.provider.to_lowercase()));
+            if let (true, Some(profile)) = (has_provider, profile) {
+                model = profile.model.clone();
+            }
+        }
+    }
+    Some(model).filter(|model| !model.is_empty())
+}
+
+fn table_entry<'a>(table: &'a toml::Table, section: &str, name: &str) -> Option<&'a toml::Table> {
+    let section = table.get(section)?.as_table()?;
+    section
+        .get(name)
+        .or_else(|| section.get(&name.to_lowercase()))?
+        .as_table()
+}
+
+/// Full provider definition, as codex would resolve `model_provider`.
+fn resolve_provider(config: &CodexConfig, toml_config: &toml::Table) -> ModelProvider {
+    let id = if config.use_oss {
+        "oss"
+    } else if config.provider.is_empty() {
+        "openai"
+    } else {
+        config.provider.as_str()
+    };
+
+    if let Some(table) = table_entry(toml_config, "model_providers", id) {
+        let mut settings = match serde_json::to_value(table) {
+            Ok(serde_json::Value::Object(settings)) => settings,
+            _ => serde_json::Map::new(),
+        };
+        let name = settings
+            .remove("name")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| id.to_string());
+        let base_url = settings
+            .remove("base_url")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .filter(|url| !url.is_empty());
+        return ModelProvider {
+            name,
+            base_url,
+            settings,
+        };
+    }
+
+    // Providers codex has built in
+    let (name, base_url, settings) = match id {
+        "openai" => (
+            "OpenAI",
+            None,
+            serde_json::json!({
+                "env_key": "OPENAI_API_KEY",
+                "wire_api": "responses",
+                "requires_openai_auth": true,
+            }),
+        ),
+        "oss" => (
+            "gpt-oss",
+            Some("http://localhost:11434/v1".to_string()),
+            serde_json::json!({ "wire_api": "chat" }),
+        ),
+        _ => (id, None, serde_json::json!({})),
+    };
+    ModelProvider {
+        name: name.to_string(),
+        base_url,
+        settings: match settings {
+            serde_json::Value::Object(settings) => settings,
+            _ => serde_json::Map::new(),
+        },
+    }
+}
+
+/// Build the `ConfigureSession` submission for a session's own settings, or
+/// None when it sets none. codex resolves its config from config.toml and the
+/// `-c` flags at startup, and `ConfigureSession` replaces all of it, so it is
+/// only sent when the session needs something the flags cannot carry.
+///
+/// Its other fields are required by the protocol. They repeat what the flags
+/// pass (`resolve_model` is shared with `LocalProcess::spawn`) and otherwise
+/// follow codex's own order: the active `profile`, the top level of
+/// config.toml, then codex's defaults. Remote sessions skip the local
+/// config.toml, which does not apply on their host. `resume_path` is left to
+/// the `experimental_resume` flag both spawn paths pass.
+pub async fn configure_session(config: &CodexConfig) -> Option<Submission> {
+    let settings = &config.session_settings;
+    if !settings.is_set() {
+        return None;
+    }
+
+    let is_remote = config
+        .connection
+        .as_ref()
+        .is_some_and(|c| c.connection_type.as_str() == "ssh");
+    let toml_config = if is_remote {
+        toml::Table::new()
+    } else {
+        read_config_table().await.unwrap_or_default()
+    };
+    let profile = toml_config
+        .get("profile")
+        .and_then(|v| v.as_str())
+        .and_then(|name| table_entry(&toml_config, "profiles", name));
+    let configured = |key: &str| {
+        profile
+            .and_then(|profile| profile.get(key))
+            .or_else(|| toml_config.get(key))
+    };
+    let configured_str = |key: &str| configured(key).and_then(|v| v.as_str()).map(str::to_string);
+
+    let model = if is_remote {
+        Some(config.model.clone()).filter(|model| !model.is_empty())
+    } else {
+        resolve_model(config).await
+    };
+    let model = model
+        .or_else(|| configured_str("model"))
+        .unwrap_or_else(|| DEFAULT_MODEL.to_string());
+    let sandbox_policy = match config.sandbox_mode.as_str() {
+        "read-only" => SandboxPolicy::ReadOnly,
+        "danger-full-access" => SandboxPolicy::DangerFullAccess,
+        _ => SandboxPolicy::WorkspaceWrite {
+            writable_roots: settings.writable_roots.clone(),
+            network_access: settings.network_access,
+        },
+    };
+    // Without a folder codex runs where it was started, as with the `-c` path.
+    // Remote sessions get the remote home filled in before they start.
+    let cwd = if config.working_directory.is_empty() {
+        std::env::current_dir().unwrap_or_default()
+    } else {
+        PathBuf::from(&config.working_directory)
+    };
+
+    Some(Submission {
+        id: Uuid::new_v4().to_string(),
+        op: Op::ConfigureSession {
+            provider: resolve_provider(config, &toml_config),
+            model,
+            model_reasoning_effort: settings
+                .reasoning_effort
+                .clone()
+                .or_else(|| configured_str("model_reasoning_effort"))
+                .unwrap_or_else(|| DEFAULT_REASONING_EFFORT.to_string()),
+            model_reasoning_summary: settings
+                .reasoning_summary
+                .clone()
+                .or_else(|| configured_str("model_reasoning_summary"))
+                .unwrap_or_else(|| DEFAULT_REASONING_SUMMARY.to_string()),
+            user_instructions: settings.user_instructions.clone(),
+            base_instructions: settings.base_instructions.clone(),
+            approval_policy: config.approval_policy.clone(),
+            sandbox_policy,
+            disable_response_storage: configured("disable_response_storage")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            cwd,
+            resume_path: None,
+        },
+    })
+}
+
 impl LocalProcess {
     pub async fn spawn(config: &CodexConfig) -> Result<ProcessHandle> {
         let (command, args): (String, Vec<String>) =
@@ -65,39 +259,20 @@ impl LocalProcess {
 
         if !config.provider.is_empty() && config.provider != "openai" {
             if let Ok(providers) = read_model_providers().await {
-                if let Ok(profiles) = read_profiles().await {
-                    let provider_config = providers.get(&config.provider)
-                        .or_else(|| providers.get(&config.provider.to_lowercase()));
-
-                    if let Some(provider_config) = provider_config {
-                        cmd.arg("-c").arg(format!("model_provider={}", provider_config.name));
-
-                        if !provider_config.base_url.is_empty() {
-                            cmd.arg("-c").arg(format!("base_url={}", provider_config.base_url));
-                        }
+                let provider_config = providers.get(&config.provider)
+                    .or_else(|| providers.get(&config.provider.to_lowercase()));
 
-                        let profile = profiles.get(&config.provider)
-                            .or_else(|| profiles.get(&config.provider.to_lowercase()));
+                if let Some(provider_config) = provider_config {
+                    cmd.arg("-c").arg(format!("model_provider={}", provider_config.name));
 
-                        let model_to_use = if let Some(profile) = profile {
-                            &profile.model
-                        } else {
-                            &config.model
-                        };
-
-                        if !model_to_use.is_empty() {
-                            cmd.arg("-c").arg(format!("model={}", model_to_use));
-                        }
+                    if !provider_config.base_url.is_empty() {
+                        cmd.arg("-c").arg(format!("base_url={}", provider_config.base_url));
+                    }
+                } else {
+                    if config.use_oss {
+                        cmd.arg("-c").arg("model_provider=oss");
                     } else {
-                        if config.use_oss {
-                            cmd.arg("-c").arg("model_provider=oss");
-                        } else {
-                            cmd.arg("-c").arg(format!("model_provider={}", config.provider));
-                        }
-
-                        if !config.model.is_empty() {
-                            cmd.arg("-c").arg(format!("model={}", config.model));
-                        }
+                        cmd.arg("-c").arg(format!("model_provider={}", config.provider));
                     }
                 }
             } else {
@@ -106,19 +281,15 @@ impl LocalProcess {
                 } else {
                     cmd.arg("-c").arg(format!("model_provider={}", config.provider));
                 }
-
-                if !config.model.is_empty() {
-                    cmd.arg("-c").arg(format!("model={}", config.model));
-                }
             }
         } else {
             if config.use_oss {
                 cmd.arg("-c").arg("model_provider=oss");
             }
+        }
 
-            if !config.model.is_empty() {
-                cmd.arg("-c").arg(format!("model={}", config.model));
-            }
+        if let Some(model) = resolve_model(config).await {
+            cmd.arg("-c").arg(format!("model={}", model));
         }
 
         if !config.approval_policy.is_empty() {
@@ -137,6 +308,10 @@ impl LocalProcess {
 
         cmd.arg("-c").arg("show_raw_agent_reasoning=true");
 
+        if let Some(resume_path) = &config.resume_path {
+            cmd.arg("-c").arg(format!("experimental_resume={}", resume_path));
+        }
+
         if !config.working_directory.is_empty() {
             cmd.arg("-c").arg(format!("cwd={}", config.working_directory));
         }
@@ -160,70 +335,541 @@ impl LocalProcess {
 
         let stdin = child.stdin.take().expect("Failed to open stdin");
         let stdout = child.stdout.take().expect("Failed to open stdout");
+        let stderr = child.stderr.take().expect("Failed to open stderr");
+
+        Ok(ProcessHandle { child, stdin, stdout, stderr })
+    }
+}
+
+/// Transport state of a session, emitted as `connection-state` so the UI can
+/// show "reconnecting" instead of a dead chat.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ConnectionState {
+    Connected,
+    Reconnecting { attempt: u32 },
+    Disconnected { reason: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionStateEvent {
+    pub session_id: String,
+    #[serde(flatten)]
+    pub state: ConnectionState,
+}
+
+/// Envelope for codex events. Flattened so the payload keeps the `{ id, msg }`
+/// shape of `Event` with the owning session added.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionEvent<'a> {
+    pub session_id: &'a str,
+    #[serde(flatten)]
+    pub event: &'a Event,
+}
+
+/// Per-session channel name, so concurrent sessions never share a stream
+pub fn session_event_name(session_id: &str) -> String {
+    format!("codex-events:{}", session_id)
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticSource {
+    Stderr,
+    Stdout,
+}
+
+/// Raw output that is not a protocol event: stderr lines and stdout lines
+/// that failed to parse. Emitted on `codex-diagnostics:{session_id}`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub session_id: String,
+    pub source: DiagnosticSource,
+    pub text: String,
+    pub timestamp: i64,
+}
+
+/// Bounded per-session history of diagnostics, kept for bug reports.
+#[derive(Clone, Default)]
+pub struct DiagnosticLog(Arc<std::sync::Mutex<VecDeque<Diagnostic>>>);
+
+impl DiagnosticLog {
+    const CAPACITY: usize = 1000;
+
+    fn push(&self, diagnostic: Diagnostic) {
+        let mut entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() == Self::CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(diagnostic);
+    }
+
+    pub fn snapshot(&self) -> Vec<Diagnostic> {
+        let entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().cloned().collect()
+    }
+
+    /// Last `n` stderr lines, oldest first
+    pub fn stderr_tail(&self, n: usize) -> Vec<String> {
+        let entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let mut tail: Vec<String> = entries
+            .iter()
+            .rev()
+            .filter(|d| matches!(d.source, DiagnosticSource::Stderr))
+            .take(n)
+            .map(|d| d.text.clone())
+            .collect();
+        tail.reverse();
+        tail
+    }
+}
+
+/// How a codex process ended, emitted as `session-terminated` when it exits
+/// without being closed from Codexia.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionTerminated {
+    pub session_id: String,
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+    pub stderr_tail: Vec<String>,
+}
+
+impl SessionTerminated {
+    fn describe(&self) -> String {
+        match (self.exit_code, self.signal) {
+            (Some(code), _) => format!("exit code {}", code),
+            (None, Some(signal)) => format!("signal {}", signal),
+            (None, None) => "unknown status".to_string(),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn exit_signal(status: &std::process::ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn exit_signal(_status: &std::process::ExitStatus) -> Option<i32> {
+    None
+}
+
+const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+const STDERR_TAIL_LINES: usize = 20;
+// ssh reports its own connection failures with this status
+const SSH_TRANSPORT_ERROR: i32 = 255;
+
+/// Everything the transport task needs to respawn codex after the link drops.
+struct Transport {
+    app: AppHandle,
+    session_id: String,
+    config: CodexConfig,
+    ssh_credentials: Option<Arc<SshCredentials>>,
+    process: Arc<Mutex<Option<Child>>>,
+    stdin: Arc<Mutex<Option<ChildStdin>>>,
+    closing: Arc<AtomicBool>,
+    /// Asks the supervisor to kill a child it is already waiting on
+    kill: Arc<Notify>,
+    /// Set while codex is working on a turn
+    turn_active: Arc<AtomicBool>,
+    rollout_path: Arc<Mutex<Option<PathBuf>>>,
+    diagnostics: DiagnosticLog,
+    exit: Arc<OnceLock<SessionTerminated>>,
+    approvals: PendingApprovals,
+    submissions: mpsc::UnboundedSender<String>,
+}
+
+impl Transport {
+    fn is_remote(&self) -> bool {
+        self.config
+            .connection
+            .as_ref()
+            .map(|c| c.connection_type.as_str() == "ssh")
+            .unwrap_or(false)
+    }
+
+    fn emit_state(&self, state: ConnectionState) {
+        let _ = self.app.emit(
+            "connection-state",
+            ConnectionStateEvent {
+                session_id: self.session_id.clone(),
+                state,
+            },
+        );
+    }
+
+    fn emit_event(&self, event: &Event) {
+        let _ = self.app.emit(
+            &session_event_name(&self.session_id),
+            SessionEvent {
+                session_id: &self.session_id,
+                event,
+            },
+        );
+    }
+
+    fn submit(&self, submission: &Submission) -> bool {
+        serde_json::to_string(submission)
+            .ok()
+            .is_some_and(|json| self.submissions.send(json).is_ok())
+    }
+
+    /// Answer exec requests covered by the project's approval rules without
+    /// asking the user. Returns true when the event was handled.
+    fn auto_approve(&self, event: &Event) -> bool {
+        let EventMsg::ExecApprovalRequest {
+            call_id,
+            command,
+            cwd,
+            ..
+        } = &event.msg
+        else {
+            return false;
+        };
+        let Some(approval) =
+            auto_approve_exec(&self.config, &self.session_id, call_id, command, cwd)
+        else {
+            return false;
+        };
+
+        let sent = self.submit(&Submission {
+            id: Uuid::new_v4().to_string(),
+            op: Op::ExecApproval {
+                id: event.id.clone(),
+                decision: approval.decision,
+            },
+        });
+        if sent {
+            let _ = self.app.emit("auto-approval", &approval);
+        }
+        sent
+    }
+
+    fn record_diagnostic(&self, source: DiagnosticSource, text: String) {
+        record_diagnostic(&self.app, &self.session_id, &self.diagnostics, source, text);
+    }
+
+    /// Drain stderr continuously; an unread pipe can fill up and block codex.
+    fn spawn_stderr_reader(&self, stderr: ChildStderr) {
+        let app = self.app.clone();
+        let session_id = self.session_id.clone();
+        let diagnostics = self.diagnostics.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                record_diagnostic(&app, &session_id, &diagnostics, DiagnosticSource::Stderr, line);
+            }
+        });
+    }
+
+    async fn run(self, mut stdout: ChildStdout, stderr: ChildStderr) {
+        self.spawn_stderr_reader(stderr);
+        loop {
+            self.pump(stdout).await;
+            let status = self.wait_for_exit().await;
+
+            if self.closing.load(Ordering::SeqCst) {
+                break;
+            }
+            // Only transport failures are worth reconnecting; a remote codex
+            // that exited on its own would just exit again
+            let transport_failed = status
+                .map(|s| s.code() == Some(SSH_TRANSPORT_ERROR) || s.code().is_none())
+                .unwrap_or(true);
+            if self.is_remote() && transport_failed {
+                log::warn!("Transport for session {} dropped", self.session_id);
+                if let Some(next) = self.reconnect().await {
+                    stdout = next;
+                    continue;
+                }
+            }
+            if !self.closing.load(Ordering::SeqCst) {
+                self.terminated(status);
+            }
+            break;
+        }
+    }
+
+    /// Wait for the child to exit once stdout has closed. The child leaves the
+    /// shared slot so the lock is not held while waiting; `close_session`
+    /// reaches it through `kill` instead. None if it was already closed.
+    async fn wait_for_exit(&self) -> Option<std::process::ExitStatus> {
+        let mut child = self.process.lock().await.take()?;
+        tokio::select! {
+            status = child.wait() => status.ok(),
+            _ = self.kill.notified() => {
+                if let Err(e) = child.kill().await {
+                    log::error!("Failed to kill codex process: {}", e);
+                }
+                child.wait().await.ok()
+            }
+        }
+    }
 
-        Ok(ProcessHandle { child, stdin, stdout })
+    fn terminated(&self, status: Option<std::process::ExitStatus>) {
+        let event = SessionTerminated {
+            session_id: self.session_id.clone(),
+            exit_code: status.and_then(|s| s.code()),
+            signal: status.as_ref().and_then(exit_signal),
+            stderr_tail: self.diagnostics.stderr_tail(STDERR_TAIL_LINES),
+        };
+        log::warn!("Session {} terminated: {}", self.session_id, event.describe());
+        let _ = self.app.emit("session-terminated", &event);
+        let _ = self.exit.set(event);
+        self.approvals.clear(&self.session_id);
+    }
+
+    async fn pump(&self, stdout: ChildStdout) {
+        let reader = BufReader::new(stdout);
+        let mut lines = reader.lines();
+        // The model codex actually runs, which may come from a profile or its defaults
+        let mut model = self.config.model.clone();
+
+        while let Ok(Some(line)) = lines.next_line().await {
+            match parse_event(&line) {
+                Ok(event) => {
+                    if let EventMsg::SessionConfigured {
+                        model: configured_model,
+                        rollout_path,
+                        ..
+                    } = &event.msg
+                    {
+                        model = configured_model.clone();
+                        if let Some(path) = rollout_path {
+                            *self.rollout_path.lock().await = Some(path.clone());
+                        }
+                    }
+                    match &event.msg {
+                        EventMsg::TaskStarted => self.turn_active.store(true, Ordering::SeqCst),
+                        EventMsg::TaskComplete { .. }
+                        | EventMsg::Error { .. }
+                        | EventMsg::ShutdownComplete => {
+                            self.turn_active.store(false, Ordering::SeqCst)
+                        }
+                        _ => {}
+                    }
+                    if let EventMsg::TokenCount { info: Some(info) } = &event.msg {
+                        let record = UsageRecord {
+                            session_id: self.session_id.clone(),
+                            model: model.clone(),
+                            provider: self.config.provider.clone(),
+                            project: project_key(&self.config),
+                            timestamp: current_timestamp_millis(),
+                            usage: info.last_token_usage.clone(),
+                        };
+                        tokio::task::spawn_blocking(move || record_usage(&record));
+                    }
+                    if self.auto_approve(&event) {
+                        continue;
+                    }
+                    self.approvals.observe(&self.session_id, &event);
+                    self.emit_event(&event);
+                }
+                Err(e) => {
+                    log::debug!("Unparsed stdout line for session {}: {}", self.session_id, e);
+                    self.record_diagnostic(DiagnosticSource::Stdout, line);
+                }
+            }
+        }
+    }
+
+    async fn reconnect(&self) -> Option<ChildStdout> {
+        // Resume from the rollout codex reported, so the conversation survives
+        let mut config = self.config.clone();
+        if let Some(path) = self.rollout_path.lock().await.clone() {
+            config.resume_path = Some(path.to_string_lossy().to_string());
+        }
+
+        for attempt in 1..=MAX_RECONNECT_ATTEMPTS {
+            self.emit_state(ConnectionState::Reconnecting { attempt });
+            tokio::time::sleep(Duration::from_secs(u64::from(attempt) * 2)).await;
+            if self.closing.load(Ordering::SeqCst) {
+                return None;
+            }
+
+            match SshProcess::spawn(&config, self.ssh_credentials.as_deref()) {
+                Ok(handle) => {
+                    if let Some(mut old) = self.process.lock().await.replace(handle.child) {
+                        let _ = old.kill().await;
+                    }
+                    *self.stdin.lock().await = Some(handle.stdin);
+                    self.spawn_stderr_reader(handle.stderr);
+                    log::info!("Session {} reconnected", self.session_id);
+                    if let Some(submission) = configure_session(&config).await {
+                        self.submit(&submission);
+                    }
+                    self.emit_state(ConnectionState::Connected);
+                    return Some(handle.stdout);
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Reconnect attempt {} for session {} failed: {}",
+                        attempt,
+                        self.session_id,
+                        e
+                    );
+                }
+            }
+        }
+
+        self.emit_state(ConnectionState::Disconnected {
+            reason: "reconnect failed".to_string(),
+        });
+        None
     }
 }
 
+fn record_diagnostic(
+    app: &AppHandle,
+    session_id: &str,
+    diagnostics: &DiagnosticLog,
+    source: DiagnosticSource,
+    text: String,
+) {
+    let diagnostic = Diagnostic {
+        session_id: session_id.to_string(),
+        source,
+        text,
+        timestamp: current_timestamp_millis(),
+    };
+    let _ = app.emit(&format!("codex-diagnostics:{}", session_id), &diagnostic);
+    diagnostics.push(diagnostic);
+}
+
 pub struct CodexClient {
     #[allow(dead_code)]
     app: AppHandle,
     session_id: String,
-    process: Option<Child>,
+    process: Arc<Mutex<Option<Child>>>,
     stdin_tx: Option<mpsc::UnboundedSender<String>>,
-    #[allow(dead_code)]
     config: CodexConfig,
+    ssh_credentials: Option<Arc<SshCredentials>>,
+    closing: Arc<AtomicBool>,
+    kill: Arc<Notify>,
+    turn_active: Arc<AtomicBool>,
+    rollout_path: Arc<Mutex<Option<PathBuf>>>,
+    diagnostics: DiagnosticLog,
+    exit: Arc<OnceLock<SessionTerminated>>,
 }
 
 impl CodexClient {
-    pub async fn new(app: &AppHandle, session_id: String, config: CodexConfig, handle: ProcessHandle) -> Result<Self> {
+    pub async fn new(
+        app: &AppHandle,
+        session_id: String,
+        config: CodexConfig,
+        handle: ProcessHandle,
+        ssh_credentials: Option<Arc<SshCredentials>>,
+        approvals: PendingApprovals,
+    ) -> Result<Self> {
         log::debug!("Creating CodexClient for session: {}", session_id);
 
-        let process = handle.child;
-        let stdin = handle.stdin;
+        let process = Arc::new(Mutex::new(Some(handle.child)));
+        let stdin = Arc::new(Mutex::new(Some(handle.stdin)));
         let stdout = handle.stdout;
+        let closing = Arc::new(AtomicBool::new(false));
+        let kill = Arc::new(Notify::new());
+        let turn_active = Arc::new(AtomicBool::new(false));
+        let diagnostics = DiagnosticLog::default();
+        let exit = Arc::new(OnceLock::new());
+        let rollout_path = Arc::new(Mutex::new(config.resume_path.clone().map(PathBuf::from)));
 
         let (stdin_tx, mut stdin_rx) = mpsc::unbounded_channel::<String>();
 
-        let mut stdin_writer = stdin;
+        // The writer looks up the current stdin per line so it keeps working
+        // after the transport reconnects
+        let stdin_writer = stdin.clone();
+        let writer_session = session_id.clone();
         tokio::spawn(async move {
             while let Some(line) = stdin_rx.recv().await {
-                if stdin_writer.write_all(line.as_bytes()).await.is_err() {
-                    break;
-                }
-                if stdin_writer.write_all(b"\n").await.is_err() {
-                    break;
+                let mut guard = stdin_writer.lock().await;
+                let Some(writer) = guard.as_mut() else {
+                    log::warn!("Dropping submission for disconnected session {}", writer_session);
+                    continue;
+                };
+                let result = async {
+                    writer.write_all(line.as_bytes()).await?;
+                    writer.write_all(b"\n").await?;
+                    writer.flush().await
                 }
-                if stdin_writer.flush().await.is_err() {
-                    break;
+                .await;
+                if result.is_err() {
+                    *guard = None;
                 }
             }
         });
 
-        let app_clone = app.clone();
-        tokio::spawn(async move {
-            let reader = BufReader::new(stdout);
-            let mut lines = reader.lines();
-
-            while let Ok(Some(line)) = lines.next_line().await {
-                if let Ok(event) = serde_json::from_str::<Event>(&line) {
-                    let _ = app_clone.emit("codex-events", &event);
-                }
-            }
-        });
+        let transport = Transport {
+            app: app.clone(),
+            session_id: session_id.clone(),
+            config: config.clone(),
+            ssh_credentials: ssh_credentials.clone(),
+            process: process.clone(),
+            stdin,
+            closing: closing.clone(),
+            kill: kill.clone(),
+            turn_active: turn_active.clone(),
+            rollout_path: rollout_path.clone(),
+            diagnostics: diagnostics.clone(),
+            exit: exit.clone(),
+            approvals,
+            submissions: stdin_tx.clone(),
+        };
+        tokio::spawn(transport.run(stdout, handle.stderr));
 
         let client = Self {
             app: app.clone(),
             session_id,
-            process: Some(process),
+            process,
             stdin_tx: Some(stdin_tx),
             config: config.clone(),
+            ssh_credentials,
+            closing,
+            kill,
+            turn_active,
+            rollout_path,
+            diagnostics,
+            exit,
         };
 
+        if let Some(submission) = configure_session(&client.config).await {
+            client.send_submission(submission).await?;
+        }
+
         Ok(client)
     }
 
+    pub fn config(&self) -> &CodexConfig {
+        &self.config
+    }
+
+    pub fn ssh_credentials(&self) -> Option<Arc<SshCredentials>> {
+        self.ssh_credentials.clone()
+    }
+
+    /// Rollout file codex records this conversation in, once it has reported one
+    pub async fn rollout_path(&self) -> Option<PathBuf> {
+        self.rollout_path.lock().await.clone()
+    }
+
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.snapshot()
+    }
+
+    /// Set once the codex process has exited on its own
+    pub fn terminated(&self) -> Option<&SessionTerminated> {
+        self.exit.get()
+    }
+
+    /// True from sending user input until codex reports the turn finished
+    pub fn turn_in_progress(&self) -> bool {
+        self.turn_active.load(Ordering::SeqCst)
+    }
+
     async fn send_submission(&self, submission: Submission) -> Result<()> {
+        if let Some(exit) = self.terminated() {
+            return Err(anyhow::anyhow!("Session has terminated ({})", exit.describe()));
+        }
         if let Some(stdin_tx) = &self.stdin_tx {
             let json = serde_json::to_string(&submission)?;
             stdin_tx.send(json)?;
@@ -232,19 +878,27 @@ impl CodexClient {
     }
 
     pub async fn send_user_input(&self, message: String) -> Result<()> {
+        self.send_user_items(vec![InputItem::Text { text: message }])
+            .await
+    }
+
+    pub async fn send_user_items(&self, items: Vec<InputItem>) -> Result<()> {
         let submission = Submission {
             id: Uuid::new_v4().to_string(),
-            op: Op::UserInput {
-                items: vec![InputItem::Text { text: message }],
-            },
+            op: Op::UserInput { items },
         };
 
-        self.send_submission(submission).await
+        self.send_submission(submission).await?;
+        // Covers the gap until codex answers with TaskStarted
+        self.turn_active.store(true, Ordering::SeqCst);
+        Ok(())
     }
 
-    pub async fn send_exec_approval(&self, approval_id: String, approved: bool) -> Result<()> {
-        let decision = if approved { "allow" } else { "deny" }.to_string();
-
+    pub async fn send_exec_approval(
+        &self,
+        approval_id: String,
+        decision: ReviewDecision,
+    ) -> Result<()> {
         let submission = Submission {
             id: Uuid::new_v4().to_string(),
             op: Op::ExecApproval {
@@ -256,10 +910,11 @@ impl CodexClient {
         self.send_submission(submission).await
     }
 
-    #[allow(dead_code)]
-    pub async fn send_patch_approval(&self, approval_id: String, approved: bool) -> Result<()> {
-        let decision = if approved { "allow" } else { "deny" }.to_string();
-
+    pub async fn send_patch_approval(
+        &self,
+        approval_id: String,
+        decision: ReviewDecision,
+    ) -> Result<()> {
         let submission = Submission {
             id: Uuid::new_v4().to_string(),
             op: Op::PatchApproval {
@@ -282,6 +937,7 @@ impl CodexClient {
 
     pub async fn close_session(&mut self) -> Result<()> {
         log::debug!("Closing session: {}", self.session_id);
+        self.closing.store(true, Ordering::SeqCst);
 
         // Send shutdown command to codex (graceful shutdown)
         let submission = Submission {
@@ -300,7 +956,8 @@ impl CodexClient {
         }
 
         // Wait a moment for graceful shutdown, then terminate process if needed
-        if let Some(mut process) = self.process.take() {
+        let process = self.process.lock().await.take();
+        if let Some(mut process) = process {
             if let Some(pid) = process.id() {
                 log::debug!("Terminating codex process with PID: {}", pid);
             }
@@ -330,12 +987,32 @@ impl CodexClient {
                     }
                 }
             }
+        } else {
+            // The transport is already waiting on the child after its stdout
+            // closed; give it the same grace period, then have it killed
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            self.kill.notify_one();
         }
 
         log::debug!("Session {} closed", self.session_id);
         Ok(())
     }
     
+    /// Kill the codex process without asking it to shut down first.
+    pub async fn kill(&self) {
+        self.closing.store(true, Ordering::SeqCst);
+        let process = self.process.lock().await.take();
+        match process {
+            Some(mut process) => {
+                if let Err(e) = process.kill().await {
+                    log::error!("Failed to kill codex process: {}", e);
+                }
+            }
+            // The transport is waiting on the child; have it do the kill
+            None => self.kill.notify_one(),
+        }
+    }
+
     #[allow(dead_code)]
     pub async fn shutdown(&mut self) -> Result<()> {
         self.close_session().await
@@ -343,6 +1020,6 @@ impl CodexClient {
 
     #[allow(dead_code)]
     pub fn is_active(&self) -> bool {
-        self.process.is_some() && self.stdin_tx.is_some()
+        !self.closing.load(Ordering::SeqCst) && self.stdin_tx.is_some() && self.terminated().is_none()
     }
 }
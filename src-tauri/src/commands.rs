@@ -1,6 +1,13 @@
-use crate::protocol::{CodexConfig, ConnectionConfig};
-use crate::services::{codex, session, ssh};
-use crate::state::CodexState;
+use crate::codex_client::Diagnostic;
+use crate::protocol::{CodexConfig, ConnectionConfig, ReviewDecision};
+use crate::services::attachments::Attachment;
+use crate::services::codex::{ApprovalRule, SettingChange};
+use crate::services::search::SearchHit;
+use crate::services::usage::{PriceTable, UsageQuery, UsageReport};
+use crate::services::session::SessionCleanup;
+use crate::services::session_cache::SessionPage;
+use crate::services::{codex, search, session, session_cache, ssh, usage};
+use crate::state::{CodexState, PendingApproval};
 use tauri::{AppHandle, State};
 use std::fs;
 
@@ -8,8 +15,17 @@ use std::fs;
 pub use crate::services::session::Conversation;
 
 #[tauri::command]
-pub async fn load_sessions_from_disk() -> Result<Vec<Conversation>, String> {
-    session::load_sessions_from_disk().await
+pub async fn load_sessions_from_disk(app: AppHandle) -> Result<Vec<Conversation>, String> {
+    session_cache::load_sessions_from_disk(app).await
+}
+
+#[tauri::command]
+pub async fn load_sessions_page(
+    app: AppHandle,
+    offset: usize,
+    limit: usize,
+) -> Result<SessionPage, String> {
+    session_cache::load_sessions_page(app, offset, limit).await
 }
 
 #[tauri::command]
@@ -18,9 +34,45 @@ pub async fn start_codex_session(
     state: State<'_, CodexState>,
     session_id: String,
     config: CodexConfig,
+    password: Option<String>,
 ) -> Result<(), String> {
     log::info!("Starting codex session: {}", session_id);
-    codex::start_codex_session(app, state, session_id, config).await
+    codex::start_codex_session(app, state, session_id, config, password).await
+}
+
+#[tauri::command]
+pub async fn resume_codex_session(
+    app: AppHandle,
+    state: State<'_, CodexState>,
+    file_path: String,
+    config: CodexConfig,
+    password: Option<String>,
+) -> Result<String, String> {
+    log::info!("Resuming codex session from: {}", file_path);
+    codex::resume_codex_session(app, state, file_path, config, password).await
+}
+
+#[tauri::command]
+pub async fn fork_session(
+    app: AppHandle,
+    state: State<'_, CodexState>,
+    file_path: String,
+    message_index: usize,
+    config: Option<CodexConfig>,
+    password: Option<String>,
+) -> Result<Conversation, String> {
+    codex::fork_session(app, state, file_path, message_index, config, password).await
+}
+
+#[tauri::command]
+pub async fn update_session_settings(
+    app: AppHandle,
+    state: State<'_, CodexState>,
+    session_id: String,
+    config: CodexConfig,
+) -> Result<Vec<SettingChange>, String> {
+    log::info!("Updating settings of session: {}", session_id);
+    codex::update_session_settings(app, state, session_id, config).await
 }
 
 #[tauri::command]
@@ -32,14 +84,57 @@ pub async fn send_message(
     codex::send_message(state, session_id, message).await
 }
 
+#[tauri::command]
+pub async fn send_message_with_attachments(
+    state: State<'_, CodexState>,
+    session_id: String,
+    message: String,
+    attachments: Vec<Attachment>,
+) -> Result<(), String> {
+    codex::send_message_with_attachments(state, session_id, message, attachments).await
+}
+
 #[tauri::command]
 pub async fn approve_execution(
     state: State<'_, CodexState>,
     session_id: String,
     approval_id: String,
-    approved: bool,
+    call_id: Option<String>,
+    decision: ReviewDecision,
+) -> Result<(), String> {
+    codex::approve_execution(state, session_id, approval_id, call_id, decision).await
+}
+
+#[tauri::command]
+pub async fn approve_patch(
+    state: State<'_, CodexState>,
+    session_id: String,
+    approval_id: String,
+    call_id: Option<String>,
+    decision: ReviewDecision,
+) -> Result<(), String> {
+    codex::approve_patch(state, session_id, approval_id, call_id, decision).await
+}
+
+#[tauri::command]
+pub async fn read_approval_rules(project_path: String) -> Result<Vec<ApprovalRule>, String> {
+    codex::read_approval_rules(project_path).await
+}
+
+#[tauri::command]
+pub async fn save_approval_rules(
+    project_path: String,
+    rules: Vec<ApprovalRule>,
 ) -> Result<(), String> {
-    codex::approve_execution(state, session_id, approval_id, approved).await
+    codex::save_approval_rules(project_path, rules).await
+}
+
+#[tauri::command]
+pub async fn get_pending_approvals(
+    state: State<'_, CodexState>,
+    session_id: String,
+) -> Result<Vec<PendingApproval>, String> {
+    codex::get_pending_approvals(state, session_id).await
 }
 
 #[tauri::command]
@@ -62,14 +157,37 @@ pub async fn get_running_sessions(state: State<'_, CodexState>) -> Result<Vec<St
     codex::get_running_sessions(state).await
 }
 
+#[tauri::command]
+pub async fn get_session_diagnostics(
+    state: State<'_, CodexState>,
+    session_id: String,
+) -> Result<Vec<Diagnostic>, String> {
+    codex::get_session_diagnostics(state, session_id).await
+}
+
 #[tauri::command]
 pub async fn check_codex_version() -> Result<String, String> {
     codex::check_codex_version().await
 }
 
 #[tauri::command]
-pub async fn test_ssh_connection(conn: ConnectionConfig) -> Result<String, String> {
-    ssh::SshProcess::test_connection(&conn)
+pub async fn check_remote_codex_version(
+    app: AppHandle,
+    conn: ConnectionConfig,
+    password: Option<String>,
+) -> Result<codex::RemoteCodexInfo, String> {
+    codex::check_remote_codex_version(app, conn, password).await
+}
+
+#[tauri::command]
+pub async fn test_ssh_connection(
+    app: AppHandle,
+    conn: ConnectionConfig,
+    password: Option<String>,
+) -> Result<String, String> {
+    let creds = ssh::SshCredentials::for_connection(&app, &conn, password)
+        .map_err(|e| e.to_string())?;
+    ssh::SshProcess::test_connection(&conn, creds.as_ref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -80,8 +198,16 @@ pub async fn delete_session_file(file_path: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub async fn get_latest_session_id() -> Result<Option<String>, String> {
-    session::get_latest_session_id().await
+pub async fn clean_up_sessions(
+    dry_run: bool,
+    min_age_minutes: Option<u64>,
+) -> Result<SessionCleanup, String> {
+    session::clean_up_sessions(dry_run, min_age_minutes).await
+}
+
+#[tauri::command]
+pub async fn get_latest_session_id(app: AppHandle) -> Result<Option<String>, String> {
+    session_cache::get_latest_session_id(app).await
 }
 
 #[tauri::command]
@@ -135,6 +261,15 @@ pub async fn read_session_file(file_path: String) -> Result<String, String> {
     fs::read_to_string(&file_path).map_err(|e| format!("Failed to read session file: {}", e))
 }
 
+#[tauri::command]
+pub async fn search_sessions(
+    app: AppHandle,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<SearchHit>, String> {
+    search::search_sessions(app, query, limit).await
+}
+
 #[tauri::command]
 pub async fn read_history_file() -> Result<String, String> {
     let home = dirs::home_dir().ok_or("Could not find home directory")?;
@@ -146,3 +281,18 @@ pub async fn read_history_file() -> Result<String, String> {
     
     fs::read_to_string(&history_path).map_err(|e| format!("Failed to read history file: {}", e))
 }
+
+#[tauri::command]
+pub async fn query_token_usage(query: UsageQuery) -> Result<UsageReport, String> {
+    usage::query_token_usage(query).await
+}
+
+#[tauri::command]
+pub async fn read_model_prices() -> Result<PriceTable, String> {
+    usage::read_model_prices().await
+}
+
+#[tauri::command]
+pub async fn save_model_prices(prices: PriceTable) -> Result<(), String> {
+    usage::save_model_prices(prices).await
+}
@@ -181,6 +181,21 @@ pub async fn read_profiles() -> Result<HashMap<String, Profile>, String> {
     Ok(config.profiles)
 }
 
+/// All of `~/.codex/config.toml`, for the settings codex reads that have no
+/// typed counterpart here.
+pub async fn read_config_table() -> Result<toml::Table, String> {
+    let config_path = get_config_path()?;
+
+    if !config_path.exists() {
+        return Ok(toml::Table::new());
+    }
+
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read config file: {}", e))?;
+
+    toml::from_str(&content).map_err(|e| format!("Failed to parse config file: {}", e))
+}
+
 #[command]
 pub async fn get_provider_config(
     provider_name: String,
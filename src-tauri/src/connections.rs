@@ -0,0 +1,232 @@
+use crate::protocol::SshAuthMode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::command;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionProfile {
+    pub host: String,
+    #[serde(default)]
+    pub user: String,
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub key_path: Option<String>,
+    #[serde(default)]
+    pub jump_host: Option<String>,
+    #[serde(default)]
+    pub codex_path: Option<String>,
+    #[serde(default)]
+    pub auth: SshAuthMode,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConnectionsConfig {
+    #[serde(default)]
+    pub connections: HashMap<String, ConnectionProfile>,
+}
+
+fn get_connections_path() -> Result<PathBuf, String> {
+    let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
+    Ok(home_dir.join(".codex").join("connections.toml"))
+}
+
+fn load_connections() -> Result<ConnectionsConfig, String> {
+    let path = get_connections_path()?;
+
+    if !path.exists() {
+        return Ok(ConnectionsConfig::default());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read connections file: {}", e))?;
+
+    toml::from_str(&content).map_err(|e| format!("Failed to parse connections file: {}", e))
+}
+
+fn save_connections(config: &ConnectionsConfig) -> Result<(), String> {
+    let path = get_connections_path()?;
+
+    let toml_content =
+        toml::to_string(config).map_err(|e| format!("Failed to serialize connections: {}", e))?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+
+    fs::write(&path, toml_content).map_err(|e| format!("Failed to write connections file: {}", e))
+}
+
+#[command]
+pub async fn read_connection_profiles() -> Result<HashMap<String, ConnectionProfile>, String> {
+    Ok(load_connections()?.connections)
+}
+
+#[command]
+pub async fn add_or_update_connection_profile(
+    profile_name: String,
+    profile: ConnectionProfile,
+) -> Result<(), String> {
+    if profile.host.trim().is_empty() {
+        return Err("Host is required".to_string());
+    }
+
+    let mut config = load_connections()?;
+    config.connections.insert(profile_name, profile);
+    save_connections(&config)
+}
+
+#[command]
+pub async fn delete_connection_profile(profile_name: String) -> Result<(), String> {
+    let mut config = load_connections()?;
+
+    if config.connections.remove(&profile_name).is_none() {
+        return Err(format!("Connection '{}' not found", profile_name));
+    }
+
+    save_connections(&config)
+}
+
+/// Parse `~/.ssh/config` Host entries into profiles that can be saved with
+/// `add_or_update_connection_profile`. Wildcard patterns and `Match` blocks are skipped.
+#[command]
+pub async fn import_ssh_config_hosts() -> Result<HashMap<String, ConnectionProfile>, String> {
+    let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
+    let path = home_dir.join(".ssh").join("config");
+
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read ssh config: {}", e))?;
+
+    Ok(parse_ssh_config(&content))
+}
+
+fn parse_ssh_config(content: &str) -> HashMap<String, ConnectionProfile> {
+    let mut hosts: HashMap<String, ConnectionProfile> = HashMap::new();
+    let mut current: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // Both `Key value` and `Key=value` are valid
+        let (key, value) = match line.split_once(|c: char| c.is_whitespace() || c == '=') {
+            Some((k, v)) => (
+                k.to_lowercase(),
+                v.trim_start_matches(|c: char| c.is_whitespace() || c == '=')
+                    .trim(),
+            ),
+            None => continue,
+        };
+        let value = value.trim_matches('"');
+
+        match key.as_str() {
+            "host" => {
+                current = value
+                    .split_whitespace()
+                    .filter(|alias| !alias.contains(['*', '?', '!']))
+                    .map(|alias| alias.to_string())
+                    .collect();
+                for alias in &current {
+                    hosts
+                        .entry(alias.clone())
+                        .or_insert_with(|| ConnectionProfile {
+                            host: alias.clone(),
+                            user: String::new(),
+                            port: None,
+                            key_path: None,
+                            jump_host: None,
+                            codex_path: None,
+                            auth: SshAuthMode::Key,
+                        });
+                }
+            }
+            "match" => current.clear(),
+            _ => {
+                for alias in &current {
+                    let Some(profile) = hosts.get_mut(alias) else {
+                        continue;
+                    };
+                    // ssh uses the first value it sees for each option
+                    match key.as_str() {
+                        "hostname" if profile.host == *alias => profile.host = value.to_string(),
+                        "user" if profile.user.is_empty() => profile.user = value.to_string(),
+                        "port" if profile.port.is_none() => profile.port = value.parse().ok(),
+                        "identityfile" if profile.key_path.is_none() => {
+                            profile.key_path = Some(value.to_string())
+                        }
+                        "proxyjump" if profile.jump_host.is_none() && value != "none" => {
+                            profile.jump_host = Some(value.to_string())
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    hosts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_lines_with_several_aliases_share_options() {
+        let hosts = parse_ssh_config(
+            "Host dev staging\n    HostName 10.0.0.5\n    User deploy\n    Port 2222\n",
+        );
+        assert_eq!(hosts.len(), 2);
+        for alias in ["dev", "staging"] {
+            let profile = &hosts[alias];
+            assert_eq!(profile.host, "10.0.0.5");
+            assert_eq!(profile.user, "deploy");
+            assert_eq!(profile.port, Some(2222));
+        }
+    }
+
+    #[test]
+    fn wildcard_hosts_and_match_blocks_are_skipped() {
+        let hosts = parse_ssh_config(
+            "Host *\n    User everyone\n\nHost box *.internal !bad\n    Port 22\n\n\
+             Match host box\n    User matched\n",
+        );
+        assert_eq!(hosts.keys().collect::<Vec<_>>(), ["box"]);
+        // Options under `Host *` come before `box` exists, and `Match` is ignored
+        assert_eq!(hosts["box"].user, "");
+        assert_eq!(hosts["box"].host, "box");
+        assert_eq!(hosts["box"].port, Some(22));
+    }
+
+    #[test]
+    fn proxy_jump_and_identity_file_are_kept() {
+        let hosts = parse_ssh_config(
+            "Host inner\n    ProxyJump bastion\n    IdentityFile ~/.ssh/id_inner\n\
+             \n    IdentityFile ~/.ssh/id_other\n\nHost direct\n    ProxyJump none\n",
+        );
+        assert_eq!(hosts["inner"].jump_host.as_deref(), Some("bastion"));
+        // ssh expands `~` in -i itself, and the first IdentityFile wins
+        assert_eq!(hosts["inner"].key_path.as_deref(), Some("~/.ssh/id_inner"));
+        assert_eq!(hosts["direct"].jump_host, None);
+    }
+
+    #[test]
+    fn keywords_are_case_insensitive_and_accept_equals() {
+        let hosts = parse_ssh_config(
+            "# comment\nHOST=box\n  hostname = example.com\n  USER=\"me\"\n  pOrT=2200\n",
+        );
+        let profile = &hosts["box"];
+        assert_eq!(profile.host, "example.com");
+        assert_eq!(profile.user, "me");
+        assert_eq!(profile.port, Some(2200));
+    }
+}
@@ -0,0 +1,63 @@
+use super::directory_ops::read_directory_local;
+use super::file_io::read_file_local;
+use super::file_types::{FileEntry, GitDiff};
+use super::git_diff::git_file_diff_local;
+use super::git_status::{git_status_local, GitStatus};
+use super::remote::RemoteFs;
+use crate::state::CodexState;
+
+/// Where the file tree, viewer, diff and status panes read from. Sessions that
+/// run over SSH see the remote host; everything else sees the local disk.
+pub enum FsBackend {
+    Local,
+    Ssh(RemoteFs),
+}
+
+impl FsBackend {
+    pub async fn for_session(state: &CodexState, session_id: Option<&str>) -> Self {
+        let Some(session_id) = session_id else {
+            return FsBackend::Local;
+        };
+        let sessions = state.sessions.lock().await;
+        let Some(client) = sessions.get(session_id) else {
+            return FsBackend::Local;
+        };
+        match client
+            .config()
+            .connection
+            .as_ref()
+            .filter(|c| c.connection_type.as_str() == "ssh")
+        {
+            Some(conn) => FsBackend::Ssh(RemoteFs::new(conn.clone(), client.ssh_credentials())),
+            None => FsBackend::Local,
+        }
+    }
+
+    pub async fn read_directory(&self, path: &str) -> Result<Vec<FileEntry>, String> {
+        match self {
+            FsBackend::Local => read_directory_local(path),
+            FsBackend::Ssh(remote) => remote.read_directory(path).await,
+        }
+    }
+
+    pub async fn read_file(&self, file_path: &str) -> Result<String, String> {
+        match self {
+            FsBackend::Local => read_file_local(file_path),
+            FsBackend::Ssh(remote) => remote.read_file(file_path).await,
+        }
+    }
+
+    pub async fn git_status(&self, directory: &str) -> Result<GitStatus, String> {
+        match self {
+            FsBackend::Local => git_status_local(directory),
+            FsBackend::Ssh(remote) => remote.git_status(directory).await,
+        }
+    }
+
+    pub async fn git_file_diff(&self, file_path: &str) -> Result<GitDiff, String> {
+        match self {
+            FsBackend::Local => git_file_diff_local(file_path),
+            FsBackend::Ssh(remote) => remote.git_file_diff(file_path).await,
+        }
+    }
+}
@@ -1,9 +1,23 @@
+use super::backend::FsBackend;
 use super::file_types::FileEntry;
+use crate::state::CodexState;
 use std::fs;
 use std::path::Path;
+use tauri::State;
 
 #[tauri::command]
-pub async fn read_directory(path: String) -> Result<Vec<FileEntry>, String> {
+pub async fn read_directory(
+    state: State<'_, CodexState>,
+    path: String,
+    session_id: Option<String>,
+) -> Result<Vec<FileEntry>, String> {
+    FsBackend::for_session(&state, session_id.as_deref())
+        .await
+        .read_directory(&path)
+        .await
+}
+
+pub fn read_directory_local(path: &str) -> Result<Vec<FileEntry>, String> {
     let expanded_path = if path.starts_with("~/") {
         let home = dirs::home_dir().ok_or_else(|| "Cannot find home directory".to_string())?;
         home.join(&path[2..])
@@ -59,14 +73,18 @@ pub async fn read_directory(path: String) -> Result<Vec<FileEntry>, String> {
         Err(e) => return Err(format!("Failed to read directory: {}", e)),
     }
 
-    // Sort directories first, then files
+    sort_entries(&mut entries);
+
+    Ok(entries)
+}
+
+/// Sort directories first, then files
+pub fn sort_entries(entries: &mut [FileEntry]) {
     entries.sort_by(|a, b| match (a.is_directory, b.is_directory) {
         (true, false) => std::cmp::Ordering::Less,
         (false, true) => std::cmp::Ordering::Greater,
         _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
     });
-
-    Ok(entries)
 }
 
 #[tauri::command]
@@ -1,8 +1,25 @@
+use super::backend::FsBackend;
+use crate::state::CodexState;
 use std::fs;
 use std::path::Path;
+use tauri::State;
+
+/// Largest file the viewer will load
+pub const MAX_READ_SIZE: u64 = 1024 * 1024;
 
 #[tauri::command]
-pub async fn read_file(file_path: String) -> Result<String, String> {
+pub async fn read_file(
+    state: State<'_, CodexState>,
+    file_path: String,
+    session_id: Option<String>,
+) -> Result<String, String> {
+    FsBackend::for_session(&state, session_id.as_deref())
+        .await
+        .read_file(&file_path)
+        .await
+}
+
+pub fn read_file_local(file_path: &str) -> Result<String, String> {
     let expanded_path = if file_path.starts_with("~/") {
         let home = dirs::home_dir().ok_or_else(|| "Cannot find home directory".to_string())?;
         home.join(&file_path[2..])
@@ -16,8 +33,7 @@ pub async fn read_file(file_path: String) -> Result<String, String> {
 
     // Check file size to prevent reading very large files
     if let Ok(metadata) = fs::metadata(&expanded_path) {
-        if metadata.len() > 1024 * 1024 {
-            // 1MB limit
+        if metadata.len() > MAX_READ_SIZE {
             return Err("File is too large to display".to_string());
         }
     }
@@ -1,10 +1,24 @@
+use super::backend::FsBackend;
 use super::file_types::GitDiff;
+use crate::state::CodexState;
 use std::fs;
 use std::path::Path;
 use std::process::Command;
+use tauri::State;
 
 #[tauri::command]
-pub async fn get_git_file_diff(file_path: String) -> Result<GitDiff, String> {
+pub async fn get_git_file_diff(
+    state: State<'_, CodexState>,
+    file_path: String,
+    session_id: Option<String>,
+) -> Result<GitDiff, String> {
+    FsBackend::for_session(&state, session_id.as_deref())
+        .await
+        .git_file_diff(&file_path)
+        .await
+}
+
+pub fn git_file_diff_local(file_path: &str) -> Result<GitDiff, String> {
     let expanded_path = if file_path.starts_with("~/") {
         let home = dirs::home_dir().ok_or_else(|| "Cannot find home directory".to_string())?;
         home.join(&file_path[2..])
@@ -1,6 +1,9 @@
+use super::backend::FsBackend;
+use crate::state::CodexState;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::process::Command;
+use tauri::State;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GitStatus {
@@ -13,7 +16,18 @@ pub struct GitStatus {
 }
 
 #[tauri::command]
-pub async fn get_git_status(directory: String) -> Result<GitStatus, String> {
+pub async fn get_git_status(
+    state: State<'_, CodexState>,
+    directory: String,
+    session_id: Option<String>,
+) -> Result<GitStatus, String> {
+    FsBackend::for_session(&state, session_id.as_deref())
+        .await
+        .git_status(&directory)
+        .await
+}
+
+pub fn git_status_local(directory: &str) -> Result<GitStatus, String> {
     let expanded_path = if directory.starts_with("~/") {
         let home = dirs::home_dir()
             .ok_or_else(|| "Cannot find home directory".to_string())?;
@@ -31,7 +45,12 @@ pub async fn get_git_status(directory: String) -> Result<GitStatus, String> {
     if !output.status.success() {
         return Err("Not a git repository or git command failed".to_string());
     }
-    
+
+    Ok(parse_porcelain(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git status --porcelain` output
+pub fn parse_porcelain(status_output: &str) -> GitStatus {
     let mut git_status = GitStatus {
         staged: Vec::new(),
         modified: Vec::new(),
@@ -41,7 +60,6 @@ pub async fn get_git_status(directory: String) -> Result<GitStatus, String> {
         conflicted: Vec::new(),
     };
     
-    let status_output = String::from_utf8_lossy(&output.stdout);
     for line in status_output.lines() {
         if line.len() < 3 {
             continue;
@@ -64,5 +82,5 @@ pub async fn get_git_status(directory: String) -> Result<GitStatus, String> {
         }
     }
     
-    Ok(git_status)
-}
\ No newline at end of file
+    git_status
+}
@@ -1,3 +1,4 @@
+pub mod backend;
 pub mod directory_ops;
 pub mod file_analysis;
 pub mod file_io;
@@ -5,3 +6,4 @@ pub mod file_parsers;
 pub mod file_types;
 pub mod git_diff;
 pub mod git_status;
+pub mod remote;
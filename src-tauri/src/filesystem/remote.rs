@@ -0,0 +1,183 @@
+use super::directory_ops::sort_entries;
+use super::file_io::MAX_READ_SIZE;
+use super::file_types::{FileEntry, GitDiff};
+use super::git_status::{parse_porcelain, GitStatus};
+use crate::protocol::ConnectionConfig;
+use crate::services::ssh::{shell_quote, SshCredentials, SshProcess};
+use std::process::Output;
+use std::sync::Arc;
+
+// Exit codes used by the remote scripts below; ssh itself exits with 255
+const EXIT_NOT_FOUND: i32 = 3;
+const EXIT_TOO_LARGE: i32 = 4;
+const EXIT_NOT_GIT: i32 = 5;
+const EXIT_SSH: i32 = 255;
+
+/// Filesystem and git access on the host of an SSH session, implemented with
+/// plain POSIX shell commands so nothing needs to be installed remotely.
+pub struct RemoteFs {
+    conn: ConnectionConfig,
+    creds: Option<Arc<SshCredentials>>,
+}
+
+impl RemoteFs {
+    pub fn new(conn: ConnectionConfig, creds: Option<Arc<SshCredentials>>) -> Self {
+        Self { conn, creds }
+    }
+
+    async fn run(&self, script: &str) -> Result<Output, String> {
+        let output = SshProcess::run_script(&self.conn, self.creds.as_deref(), script)
+            .await
+            .map_err(|e| format!("Failed to run remote command: {}", e))?;
+        if output.status.code() == Some(EXIT_SSH) {
+            let err = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(format!("SSH connection failed: {}", err));
+        }
+        Ok(output)
+    }
+
+    pub async fn read_directory(&self, path: &str) -> Result<Vec<FileEntry>, String> {
+        let script = format!(
+            r#"cd -- {} 2>/dev/null || exit {}
+pwd
+for f in * .[!.]* ..?*; do
+  [ -e "$f" ] || [ -L "$f" ] || continue
+  if [ -d "$f" ]; then printf 'd\t\t%s\n' "$f"
+  else printf 'f\t%s\t%s\n' "$(wc -c < "$f" 2>/dev/null | tr -d ' ')" "$f"; fi
+done"#,
+            shell_quote(path),
+            EXIT_NOT_FOUND
+        );
+        let output = self.run(&script).await?;
+        if !output.status.success() {
+            return Err("Directory does not exist".to_string());
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines();
+        let cwd = lines.next().unwrap_or(path).trim_end_matches('/');
+
+        let mut entries: Vec<FileEntry> = lines
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                let kind = parts.next()?;
+                let size = parts.next()?;
+                let name = parts.next()?.to_string();
+                let is_directory = kind == "d";
+                let extension = if is_directory {
+                    None
+                } else {
+                    std::path::Path::new(&name)
+                        .extension()
+                        .and_then(|ext| ext.to_str())
+                        .map(|s| s.to_string())
+                };
+                Some(FileEntry {
+                    path: format!("{}/{}", cwd, name),
+                    name,
+                    is_directory,
+                    size: if is_directory { None } else { size.parse().ok() },
+                    extension,
+                })
+            })
+            .collect();
+
+        sort_entries(&mut entries);
+
+        Ok(entries)
+    }
+
+    pub async fn read_file(&self, file_path: &str) -> Result<String, String> {
+        let script = format!(
+            r#"f={}
+[ -f "$f" ] || exit {}
+[ "$(wc -c < "$f")" -le {} ] || exit {}
+cat -- "$f""#,
+            shell_quote(file_path),
+            EXIT_NOT_FOUND,
+            MAX_READ_SIZE,
+            EXIT_TOO_LARGE
+        );
+        let output = self.run(&script).await?;
+        match output.status.code() {
+            Some(0) => String::from_utf8(output.stdout)
+                .map_err(|e| format!("Failed to read file: {}", e)),
+            Some(EXIT_NOT_FOUND) => Err("File does not exist or is a directory".to_string()),
+            Some(EXIT_TOO_LARGE) => Err("File is too large to display".to_string()),
+            _ => Err(format!(
+                "Failed to read file: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )),
+        }
+    }
+
+    pub async fn git_status(&self, directory: &str) -> Result<GitStatus, String> {
+        let script = format!(
+            "cd -- {} || exit {}\ngit status --porcelain",
+            shell_quote(directory),
+            EXIT_NOT_FOUND
+        );
+        let output = self.run(&script).await?;
+        if !output.status.success() {
+            return Err("Not a git repository or git command failed".to_string());
+        }
+
+        Ok(parse_porcelain(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    pub async fn git_file_diff(&self, file_path: &str) -> Result<GitDiff, String> {
+        let quoted = shell_quote(file_path);
+
+        let current = self
+            .run(&format!(
+                "f={}\n[ -e \"$f\" ] || exit {}\ncat -- \"$f\"",
+                quoted, EXIT_NOT_FOUND
+            ))
+            .await?;
+        match current.status.code() {
+            Some(0) => {}
+            Some(EXIT_NOT_FOUND) => return Err("File does not exist".to_string()),
+            _ => {
+                return Err(format!(
+                    "Failed to read current file: {}",
+                    String::from_utf8_lossy(&current.stderr).trim()
+                ))
+            }
+        }
+        let current_content = String::from_utf8_lossy(&current.stdout).to_string();
+
+        // `HEAD:./name` resolves relative to the file's own directory
+        let original = self
+            .run(&format!(
+                r#"f={}
+cd -- "$(dirname -- "$f")" || exit {}
+git rev-parse --is-inside-work-tree >/dev/null 2>&1 || exit {}
+git show "HEAD:./$(basename -- "$f")""#,
+                quoted, EXIT_NOT_FOUND, EXIT_NOT_GIT
+            ))
+            .await?;
+
+        if original.status.code() == Some(EXIT_NOT_GIT) {
+            return Ok(GitDiff {
+                original_content: String::new(),
+                current_content,
+                has_changes: false,
+            });
+        }
+
+        // If git show fails, it might be a new file
+        let original_content = if original.status.success() {
+            String::from_utf8_lossy(&original.stdout).to_string()
+        } else {
+            String::new()
+        };
+
+        let has_changes = original_content != current_content;
+
+        Ok(GitDiff {
+            original_content,
+            current_content,
+            has_changes,
+        })
+    }
+}
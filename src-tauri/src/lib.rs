@@ -1,6 +1,7 @@
 mod codex_client;
 mod commands;
 mod config;
+mod connections;
 mod filesystem;
 mod protocol;
 mod services;
@@ -8,16 +9,24 @@ mod state;
 mod utils;
 
 use commands::{
-    approve_execution, check_codex_version, close_session, delete_session_file,
-    get_latest_session_id, get_running_sessions, get_session_files, load_sessions_from_disk,
-    pause_session, read_history_file, read_session_file, send_message, start_codex_session,
-    stop_session, test_ssh_connection,
+    approve_execution, approve_patch, check_codex_version, check_remote_codex_version,
+    clean_up_sessions, close_session, delete_session_file, fork_session, get_latest_session_id,
+    get_pending_approvals, get_running_sessions, get_session_diagnostics, get_session_files,
+    load_sessions_from_disk, load_sessions_page, pause_session, query_token_usage,
+    read_approval_rules, read_history_file, read_model_prices, read_session_file,
+    resume_codex_session, save_approval_rules, save_model_prices, search_sessions, send_message,
+    send_message_with_attachments, start_codex_session, stop_session, test_ssh_connection,
+    update_session_settings,
 };
 use config::{
     add_mcp_server, add_or_update_model_provider, add_or_update_profile, delete_mcp_server,
     delete_profile, get_profile_config, get_project_name, get_provider_config, read_codex_config,
     read_mcp_servers, read_model_providers, read_profiles, update_profile_model,
 };
+use connections::{
+    add_or_update_connection_profile, delete_connection_profile, import_ssh_config_hosts,
+    read_connection_profiles,
+};
 use filesystem::{
     directory_ops::{get_default_directories, read_directory},
     file_analysis::calculate_file_tokens,
@@ -57,21 +66,41 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .manage(CodexState::new())
+        .setup(|app| {
+            services::watcher::start_session_watcher(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             start_codex_session,
+            resume_codex_session,
+            fork_session,
+            update_session_settings,
             send_message,
+            send_message_with_attachments,
             approve_execution,
+            approve_patch,
+            get_pending_approvals,
+            read_approval_rules,
+            save_approval_rules,
             stop_session,
             pause_session,
             close_session,
             get_running_sessions,
+            get_session_diagnostics,
             load_sessions_from_disk,
+            load_sessions_page,
             delete_session_file,
+            clean_up_sessions,
             get_latest_session_id,
             get_session_files,
             read_session_file,
             read_history_file,
+            search_sessions,
+            query_token_usage,
+            read_model_prices,
+            save_model_prices,
             check_codex_version,
+            check_remote_codex_version,
             test_ssh_connection,
             read_directory,
             get_default_directories,
@@ -96,6 +125,10 @@ pub fn run() {
             add_or_update_profile,
             delete_profile,
             add_or_update_model_provider,
+            read_connection_profiles,
+            add_or_update_connection_profile,
+            delete_connection_profile,
+            import_ssh_config_hosts,
         ])
         .run(ctx)
         .expect("error while running tauri application");
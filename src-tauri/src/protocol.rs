@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Submission {
@@ -29,19 +31,36 @@ pub enum Op {
     Interrupt,
     ExecApproval {
         id: String,
-        decision: String,
+        decision: ReviewDecision,
     },
     PatchApproval {
         id: String,
-        decision: String,
+        decision: ReviewDecision,
     },
     Shutdown,
 }
 
+/// Answer to an exec or patch approval request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Approved,
+    /// Approve, and auto-approve identical requests for the rest of the session
+    ApprovedForSession,
+    #[default]
+    Denied,
+    /// Deny and stop the current task
+    Abort,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelProvider {
     pub name: String,
     pub base_url: Option<String>,
+    /// Everything else codex knows about the provider: `env_key`, `wire_api`,
+    /// query params, headers and retry settings
+    #[serde(flatten)]
+    pub settings: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +75,8 @@ pub enum SandboxPolicy {
         #[serde(default)]
         network_access: bool,
     },
+    #[serde(rename = "danger-full-access")]
+    DangerFullAccess,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +84,7 @@ pub enum SandboxPolicy {
 pub enum InputItem {
     Text { text: String },
     Image { image_url: String },
+    LocalImage { path: PathBuf },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,8 +99,10 @@ pub enum EventMsg {
     SessionConfigured {
         session_id: String,
         model: String,
-        history_log_id: Option<u32>,
+        history_log_id: Option<u64>,
         history_entry_count: Option<u32>,
+        #[serde(default)]
+        rollout_path: Option<PathBuf>,
     },
     TaskStarted,
     TaskComplete {
@@ -92,13 +116,34 @@ pub enum EventMsg {
     AgentMessageDelta {
         delta: String,
     },
+    AgentReasoning {
+        text: String,
+    },
+    AgentReasoningDelta {
+        delta: String,
+    },
+    AgentReasoningRawContent {
+        text: String,
+    },
+    AgentReasoningRawContentDelta {
+        delta: String,
+    },
+    AgentReasoningSectionBreak,
+    TokenCount {
+        info: Option<TokenUsageInfo>,
+    },
     ExecApprovalRequest {
-        command: String,
+        call_id: String,
+        command: Vec<String>,
         cwd: String,
+        reason: Option<String>,
     },
-    PatchApprovalRequest {
-        patch: String,
-        files: Vec<String>,
+    ApplyPatchApprovalRequest {
+        call_id: String,
+        changes: HashMap<PathBuf, FileChange>,
+        reason: Option<String>,
+        /// Directory codex asks to be allowed to write to for the rest of the session
+        grant_root: Option<PathBuf>,
     },
     Error {
         message: String,
@@ -122,10 +167,155 @@ pub enum EventMsg {
         stderr: String,
         exit_code: i32,
     },
+    McpToolCallBegin {
+        call_id: String,
+        invocation: McpInvocation,
+    },
+    McpToolCallEnd {
+        call_id: String,
+        invocation: McpInvocation,
+        duration: Duration,
+        /// The MCP `CallToolResult` on success, otherwise an error message
+        result: Result<serde_json::Value, String>,
+    },
+    WebSearchBegin {
+        call_id: String,
+    },
+    WebSearchEnd {
+        call_id: String,
+        query: String,
+    },
+    PatchApplyBegin {
+        call_id: String,
+        auto_approved: bool,
+        changes: HashMap<PathBuf, FileChange>,
+    },
+    PatchApplyEnd {
+        call_id: String,
+        stdout: String,
+        stderr: String,
+        success: bool,
+    },
+    TurnDiff {
+        unified_diff: String,
+    },
+    PlanUpdate {
+        explanation: Option<String>,
+        plan: Vec<PlanItem>,
+    },
+    GetHistoryEntryResponse {
+        offset: usize,
+        log_id: u64,
+        entry: Option<HistoryEntry>,
+    },
     ShutdownComplete,
     BackgroundEvent {
         message: String,
     },
+    StreamError {
+        message: String,
+    },
+    /// Any event this version does not model yet, kept as the raw `msg`
+    /// object (including its `type`) so newer codex releases degrade gracefully.
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub cached_input_tokens: u64,
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub reasoning_output_tokens: u64,
+    pub total_tokens: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenUsageInfo {
+    /// Running total for the session
+    pub total_token_usage: TokenUsage,
+    /// Usage of the most recent model response
+    pub last_token_usage: TokenUsage,
+    #[serde(default)]
+    pub model_context_window: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpInvocation {
+    pub server: String,
+    pub tool: String,
+    #[serde(default)]
+    pub arguments: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChange {
+    Add {
+        content: String,
+    },
+    Delete {
+        #[serde(default)]
+        content: Option<String>,
+    },
+    Update {
+        unified_diff: String,
+        move_path: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Pending,
+    InProgress,
+    Completed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanItem {
+    pub step: String,
+    pub status: StepStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub session_id: String,
+    pub ts: u64,
+    pub text: String,
+}
+
+/// Parse one line of `codex proto` output. Events whose payload no longer
+/// matches the typed variant are kept as `EventMsg::Unknown` instead of being lost.
+pub fn parse_event(line: &str) -> serde_json::Result<Event> {
+    match serde_json::from_str::<Event>(line) {
+        Ok(Event {
+            msg: EventMsg::Unknown(msg),
+            ..
+        }) if msg.get("type").is_none() => Err(serde::de::Error::missing_field("type")),
+        Ok(event) => Ok(event),
+        Err(err) => {
+            let value: serde_json::Value = serde_json::from_str(line)?;
+            match (value.get("id").and_then(|v| v.as_str()), value.get("msg")) {
+                (Some(id), Some(msg)) if msg.get("type").is_some() => Ok(Event {
+                    id: id.to_string(),
+                    msg: EventMsg::Unknown(msg.clone()),
+                }),
+                _ => Err(err),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SshAuthMode {
+    #[default]
+    Key,
+    Agent,
+    Password,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -141,6 +331,12 @@ pub struct ConnectionConfig {
     pub port: Option<u16>,
     #[serde(default)]
     pub key_path: Option<String>,
+    #[serde(default)]
+    pub auth: SshAuthMode,
+    #[serde(default)]
+    pub jump_host: Option<String>,
+    #[serde(default)]
+    pub codex_path: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -155,4 +351,39 @@ pub struct CodexConfig {
     pub codex_path: Option<String>,
     pub api_key: Option<String>,
     pub connection: Option<ConnectionConfig>,
+    #[serde(default)]
+    pub resume_path: Option<String>,
+    #[serde(default)]
+    pub session_settings: SessionSettings,
+}
+
+/// Settings codex receives through `Op::ConfigureSession` rather than `-c` flags,
+/// so each session can use its own.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionSettings {
+    #[serde(default)]
+    pub reasoning_effort: Option<String>,
+    #[serde(default)]
+    pub reasoning_summary: Option<String>,
+    #[serde(default)]
+    pub user_instructions: Option<String>,
+    #[serde(default)]
+    pub base_instructions: Option<String>,
+    /// Extra directories writable in `workspace-write` mode
+    #[serde(default)]
+    pub writable_roots: Vec<PathBuf>,
+    #[serde(default)]
+    pub network_access: bool,
+}
+
+impl SessionSettings {
+    /// True when the session sets anything, so it needs a `ConfigureSession`
+    pub fn is_set(&self) -> bool {
+        self.reasoning_effort.is_some()
+            || self.reasoning_summary.is_some()
+            || self.user_instructions.is_some()
+            || self.base_instructions.is_some()
+            || !self.writable_roots.is_empty()
+            || self.network_access
+    }
 }
@@ -0,0 +1,99 @@
+use crate::filesystem::backend::FsBackend;
+use crate::protocol::InputItem;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+// Larger images are rejected by the model API anyway
+const MAX_IMAGE_SIZE: u64 = 20 * 1024 * 1024;
+
+/// Something sent along with the text of a prompt.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Attachment {
+    /// Image file on this machine, e.g. picked from a file dialog
+    Image { path: String },
+    /// Image that is already encoded as a `data:` URL, e.g. a pasted screenshot
+    ImageData { url: String },
+    /// File from the file tree, read from the same host the file tree shows
+    File { path: String },
+}
+
+fn image_mime(path: &str) -> Option<&'static str> {
+    let ext = Path::new(path).extension()?.to_str()?.to_lowercase();
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+async fn local_image(backend: &FsBackend, path: &str) -> Result<InputItem, String> {
+    let mime = image_mime(path).ok_or_else(|| format!("Unsupported image type: {}", path))?;
+    let metadata = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| format!("Failed to read image {}: {}", path, e))?;
+    if metadata.len() > MAX_IMAGE_SIZE {
+        return Err(format!("Image is too large: {}", path));
+    }
+
+    match backend {
+        // codex reads and encodes the file itself
+        FsBackend::Local => Ok(InputItem::LocalImage {
+            path: PathBuf::from(path),
+        }),
+        // A remote codex cannot see files on this machine, so send the bytes inline
+        // Reading and encoding up to MAX_IMAGE_SIZE stays off the async runtime
+        FsBackend::Ssh(_) => {
+            let path = path.to_string();
+            tokio::task::spawn_blocking(move || {
+                let bytes = std::fs::read(&path)
+                    .map_err(|e| format!("Failed to read image {}: {}", path, e))?;
+                Ok(InputItem::Image {
+                    image_url: format!("data:{};base64,{}", mime, STANDARD.encode(bytes)),
+                })
+            })
+            .await
+            .map_err(|e| format!("Failed to read image: {}", e))?
+        }
+    }
+}
+
+async fn file_item(backend: &FsBackend, path: &str) -> Result<InputItem, String> {
+    if image_mime(path).is_some() {
+        return match backend {
+            FsBackend::Local => local_image(backend, path).await,
+            FsBackend::Ssh(_) => Err(format!("Cannot attach remote image: {}", path)),
+        };
+    }
+
+    let content = backend.read_file(path).await?;
+    Ok(InputItem::Text {
+        text: format!("Contents of {}:\n```\n{}\n```", path, content),
+    })
+}
+
+/// Turn attachments into codex input items, in the order they were given.
+pub async fn resolve_attachments(
+    backend: &FsBackend,
+    attachments: Vec<Attachment>,
+) -> Result<Vec<InputItem>, String> {
+    let mut items = Vec::with_capacity(attachments.len());
+    for attachment in attachments {
+        let item = match attachment {
+            Attachment::Image { path } => local_image(backend, &path).await?,
+            Attachment::ImageData { url } => {
+                if !url.starts_with("data:image/") {
+                    return Err("Pasted data is not an image".to_string());
+                }
+                InputItem::Image { image_url: url }
+            }
+            Attachment::File { path } => file_item(backend, &path).await?,
+        };
+        items.push(item);
+    }
+    Ok(items)
+}
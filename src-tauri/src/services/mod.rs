@@ -1,3 +1,8 @@
+pub mod attachments;
 pub mod codex;
+pub mod search;
 pub mod session;
+pub mod session_cache;
 pub mod ssh;
+pub mod usage;
+pub mod watcher;
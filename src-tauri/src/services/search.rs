@@ -0,0 +1,339 @@
+use super::session;
+use anyhow::{anyhow, Result};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+const DEFAULT_LIMIT: usize = 50;
+// The session watcher keeps the index current between full rescans, which
+// only catch what it missed
+const FULL_SYNC_INTERVAL: Duration = Duration::from_secs(300);
+
+static LAST_FULL_SYNC: Mutex<Option<Instant>> = Mutex::new(None);
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS files (
+    path TEXT PRIMARY KEY,
+    session_id TEXT NOT NULL,
+    mtime INTEGER NOT NULL,
+    size INTEGER NOT NULL
+);
+CREATE VIRTUAL TABLE IF NOT EXISTS entries USING fts5(
+    text,
+    kind UNINDEXED,
+    path UNINDEXED,
+    session_id UNINDEXED,
+    message_index UNINDEXED,
+    tokenize = 'unicode61'
+);
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HitKind {
+    Message,
+    Command,
+    File,
+}
+
+impl HitKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            HitKind::Message => "message",
+            HitKind::Command => "command",
+            HitKind::File => "file",
+        }
+    }
+
+    fn parse(kind: &str) -> Self {
+        match kind {
+            "command" => HitKind::Command,
+            "file" => HitKind::File,
+            _ => HitKind::Message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    /// Same `codex-event-` id the session list uses
+    pub session_id: String,
+    pub file_path: String,
+    pub kind: HitKind,
+    /// Index into the conversation's messages. Commands and files point at
+    /// the message they followed.
+    pub message_index: Option<usize>,
+    /// Matching text with the matched terms wrapped in « »
+    pub snippet: String,
+    /// Higher is better
+    pub score: f64,
+}
+
+struct IndexEntry {
+    kind: HitKind,
+    text: String,
+    message_index: Option<usize>,
+}
+
+fn index_path(app: &AppHandle) -> Result<PathBuf> {
+    let dir = app.path().app_data_dir()?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join("search_index.sqlite"))
+}
+
+fn open_index(path: &Path) -> Result<Connection> {
+    let conn = Connection::open(path)?;
+    // Searches from several windows may sync at the same time
+    conn.busy_timeout(Duration::from_secs(10))?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+/// Shell command of a tool call, with `apply_patch` input included so the
+/// patched paths can be picked out of it.
+fn command_text(item: &Value) -> Option<String> {
+    let join = |parts: &Value| {
+        parts.as_array().map(|parts| {
+            parts
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+    };
+
+    match item["type"].as_str()? {
+        "function_call" => {
+            let name = item["name"].as_str().unwrap_or_default();
+            let args: Value = serde_json::from_str(item["arguments"].as_str()?).ok()?;
+            join(&args["command"]).or_else(|| {
+                args["input"]
+                    .as_str()
+                    .map(|input| format!("{} {}", name, input))
+            })
+        }
+        "local_shell_call" => join(&item["action"]["command"]),
+        _ => None,
+    }
+}
+
+fn extract_entries(content: &str) -> Vec<IndexEntry> {
+    let mut entries = Vec::new();
+    let mut message_count = 0;
+
+    for line in content.lines() {
+        if let Some((_, text)) = session::line_message(line) {
+            entries.push(IndexEntry {
+                kind: HitKind::Message,
+                text,
+                message_index: Some(message_count),
+            });
+            message_count += 1;
+            continue;
+        }
+
+        let Ok(mut item) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        // Newer rollouts wrap each item
+        if item["type"] == "response_item" {
+            item = item["payload"].take();
+        }
+        let Some(command) = command_text(&item) else {
+            continue;
+        };
+
+        let message_index = message_count.checked_sub(1);
+        for path in session::patched_files(&command) {
+            entries.push(IndexEntry {
+                kind: HitKind::File,
+                text: path,
+                message_index,
+            });
+        }
+        entries.push(IndexEntry {
+            kind: HitKind::Command,
+            text: command,
+            message_index,
+        });
+    }
+
+    entries
+}
+
+/// Replace the index entries of one rollout file with its current content.
+fn index_file(
+    tx: &rusqlite::Transaction,
+    path: &str,
+    stamp: (i64, i64),
+    content: &str,
+) -> Result<()> {
+    tx.execute("DELETE FROM entries WHERE path = ?1", [path])?;
+    let session_id = session::rollout_session_id(content)
+        .map(|id| format!("codex-event-{}", id))
+        .unwrap_or_else(|| path.to_string());
+    for item in extract_entries(content) {
+        tx.execute(
+            "INSERT INTO entries (text, kind, path, session_id, message_index)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                item.text,
+                item.kind.as_str(),
+                path,
+                session_id,
+                item.message_index.map(|i| i as i64)
+            ],
+        )?;
+    }
+    tx.execute(
+        "INSERT OR REPLACE INTO files (path, session_id, mtime, size) VALUES (?1, ?2, ?3, ?4)",
+        params![path, session_id, stamp.0, stamp.1],
+    )?;
+    Ok(())
+}
+
+fn forget_file(tx: &rusqlite::Transaction, path: &str) -> Result<()> {
+    tx.execute("DELETE FROM entries WHERE path = ?1", [path])?;
+    tx.execute("DELETE FROM files WHERE path = ?1", [path])?;
+    Ok(())
+}
+
+/// Bring the index up to date with the rollout files on disk. Only files
+/// that are new or whose mtime or size changed get parsed again.
+fn sync_index(conn: &mut Connection, sessions_dir: &Path) -> Result<()> {
+    let known: HashMap<String, (i64, i64)> = {
+        let mut stmt = conn.prepare("SELECT path, mtime, size FROM files")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?;
+        rows.collect::<rusqlite::Result<_>>()?
+    };
+
+    let tx = conn.transaction()?;
+    let mut seen = HashSet::new();
+    for file in session::rollout_files(sessions_dir) {
+        let Some(path) = file.path.to_str().map(str::to_string) else {
+            continue;
+        };
+        let stamp = (file.mtime, file.size);
+        seen.insert(path.clone());
+        if known.get(&path) == Some(&stamp) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&file.path) else {
+            continue;
+        };
+        index_file(&tx, &path, stamp, &content)?;
+    }
+
+    for path in known.keys().filter(|path| !seen.contains(*path)) {
+        forget_file(&tx, path)?;
+    }
+
+    tx.commit()?;
+    Ok(())
+}
+
+/// Reindex rollout files the session watcher saw change, or drop them when
+/// they are gone.
+fn sync_paths(conn: &mut Connection, paths: &[PathBuf]) -> Result<()> {
+    let tx = conn.transaction()?;
+    for file in paths {
+        let Some(path) = file.to_str() else {
+            continue;
+        };
+        let Ok(metadata) = fs::metadata(file) else {
+            forget_file(&tx, path)?;
+            continue;
+        };
+        let mtime = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or_default();
+        let Ok(content) = fs::read_to_string(file) else {
+            continue;
+        };
+        index_file(&tx, path, (mtime, metadata.len() as i64), &content)?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Called by the session watcher with the rollout files that changed.
+pub fn update_index(app: &AppHandle, paths: &[PathBuf]) {
+    if paths.is_empty() {
+        return;
+    }
+    let result = index_path(app).and_then(|index| sync_paths(&mut open_index(&index)?, paths));
+    if let Err(e) = result {
+        log::warn!("Failed to update search index: {}", e);
+    }
+}
+
+/// Turn free text into an FTS5 query: every word must appear, the last one
+/// may be a prefix of a longer word so results follow typing.
+fn match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", terms.join(" ")))
+}
+
+fn run_search(index: &Path, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+    let mut conn = open_index(index)?;
+    let mut last_full_sync = LAST_FULL_SYNC.lock().unwrap_or_else(|e| e.into_inner());
+    if last_full_sync.is_none_or(|at| at.elapsed() >= FULL_SYNC_INTERVAL) {
+        sync_index(&mut conn, &session::sessions_dir().map_err(|e| anyhow!(e))?)?;
+        *last_full_sync = Some(Instant::now());
+    }
+    drop(last_full_sync);
+
+    let Some(expression) = match_expression(query) else {
+        return Ok(Vec::new());
+    };
+    let mut stmt = conn.prepare(
+        "SELECT session_id, path, kind, message_index,
+                snippet(entries, 0, '«', '»', '…', 16), bm25(entries)
+         FROM entries WHERE entries MATCH ?1
+         ORDER BY bm25(entries) LIMIT ?2",
+    )?;
+    let hits = stmt.query_map(params![expression, limit as i64], |row| {
+        let kind: String = row.get(2)?;
+        let message_index: Option<i64> = row.get(3)?;
+        let rank: f64 = row.get(5)?;
+        Ok(SearchHit {
+            session_id: row.get(0)?,
+            file_path: row.get(1)?,
+            kind: HitKind::parse(&kind),
+            message_index: message_index.map(|i| i as usize),
+            snippet: row.get(4)?,
+            // bm25 ranks better matches lower
+            score: -rank,
+        })
+    })?;
+    Ok(hits.collect::<rusqlite::Result<_>>()?)
+}
+
+pub async fn search_sessions(
+    app: AppHandle,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<SearchHit>, String> {
+    let index = index_path(&app).map_err(|e| format!("Failed to open search index: {}", e))?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+    tokio::task::spawn_blocking(move || run_search(&index, &query, limit))
+        .await
+        .map_err(|e| format!("Search failed: {}", e))?
+        .map_err(|e| format!("Search failed: {}", e))
+}
@@ -1,14 +1,74 @@
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::UNIX_EPOCH;
 use walkdir::WalkDir;
 
-#[derive(Debug, Serialize, Deserialize)]
+// Files changed more recently may still be written by a starting codex process
+const DEFAULT_CLEANUP_MIN_AGE_MINUTES: u64 = 10;
+
+// Tool output beyond this is cut, so huge logs do not bloat the session list
+const MAX_OUTPUT_CHARS: usize = 20_000;
+
+// File headers of the patches codex applies
+static PATCH_FILE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^\*\*\* (?:Add File|Update File|Delete File|Move to): (.+)$").unwrap()
+});
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
     pub id: String,
     pub role: String,
     pub content: String,
     pub timestamp: i64,
+    /// What happened after this message and before the next one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<TurnItem>,
+}
+
+/// A rollout record other than a chat message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum TurnItem {
+    Exec {
+        call_id: String,
+        command: Vec<String>,
+        output: Option<String>,
+        exit_code: Option<i32>,
+        duration_seconds: Option<f64>,
+    },
+    Patch {
+        call_id: String,
+        patch: String,
+        files: Vec<String>,
+        output: Option<String>,
+        success: Option<bool>,
+    },
+    Reasoning {
+        summary: String,
+    },
+    McpToolCall {
+        call_id: String,
+        server: String,
+        tool: String,
+        arguments: Value,
+        output: Option<String>,
+        success: Option<bool>,
+    },
+    /// Any other tool, such as `update_plan`
+    ToolCall {
+        call_id: String,
+        name: String,
+        arguments: String,
+        output: Option<String>,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -37,6 +97,335 @@ struct SessionRecord {
     content: Option<serde_json::Value>,
 }
 
+/// A rollout file, stamped so caches can tell when it changed.
+pub struct RolloutFile {
+    pub path: PathBuf,
+    /// Modification time in milliseconds
+    pub mtime: i64,
+    pub size: i64,
+}
+
+pub fn sessions_dir() -> Result<PathBuf, String> {
+    let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
+    Ok(home_dir.join(".codex").join("sessions"))
+}
+
+/// Every `.jsonl` file under `sessions_dir`, without reading any of them.
+pub fn rollout_files(sessions_dir: &Path) -> Vec<RolloutFile> {
+    WalkDir::new(sessions_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let mtime = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as i64)
+                .unwrap_or_default();
+            Some(RolloutFile {
+                path: entry.into_path(),
+                mtime,
+                size: metadata.len() as i64,
+            })
+        })
+        .collect()
+}
+
+/// Session id recorded in the first line of a rollout file. Newer codex
+/// versions nest it in a `session_meta` payload.
+pub fn rollout_session_id(content: &str) -> Option<String> {
+    let first_line: serde_json::Value = serde_json::from_str(content.lines().next()?).ok()?;
+    first_line
+        .get("id")
+        .or_else(|| first_line.get("payload").and_then(|p| p.get("id")))
+        .and_then(|id| id.as_str())
+        .map(|id| id.trim_start_matches("codex-event-").to_string())
+}
+
+/// Role and text of a rollout record that is shown as a chat message.
+fn record_message(record: SessionRecord) -> Option<(String, String)> {
+    // Parse messages (check for "type": "message")
+    if record.message_type.as_deref() != Some("message") {
+        return None;
+    }
+    let role = record.role?;
+    let content_value = record.content?;
+
+    let content_text = if let Some(array) = content_value.as_array() {
+        array
+            .iter()
+            .filter_map(|item| {
+                if let Some(obj) = item.as_object() {
+                    if let Some(text) = obj.get("text") {
+                        text.as_str()
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("")
+    } else if let Some(text) = content_value.as_str() {
+        text.to_string()
+    } else {
+        String::new()
+    };
+
+    if content_text.trim().is_empty() {
+        return None;
+    }
+    Some((role, content_text))
+}
+
+/// Role and text of a rollout line that shows up as a chat message, in the
+/// same order `parse_session_file` lists them.
+pub fn line_message(line: &str) -> Option<(String, String)> {
+    unwrap_line(line)
+        .and_then(|record| serde_json::from_value::<SessionRecord>(record).ok())
+        .and_then(record_message)
+}
+
+/// A rollout line without the `response_item` / `session_meta` envelope newer
+/// codex versions put around each record, so both formats read the same.
+fn unwrap_line(line: &str) -> Option<Value> {
+    let mut record: Value = serde_json::from_str(line).ok()?;
+    if matches!(
+        record["type"].as_str(),
+        Some("response_item" | "session_meta")
+    ) {
+        record = record["payload"].take();
+    }
+    Some(record)
+}
+
+/// Paths named in the file headers of an `apply_patch` patch.
+pub fn patched_files(patch: &str) -> Vec<String> {
+    PATCH_FILE
+        .captures_iter(patch)
+        .map(|c| c[1].trim().to_string())
+        .collect()
+}
+
+/// A rollout line that starts a tool call or reasoning step, or finishes a call.
+enum ItemLine {
+    Item(TurnItem),
+    Output { call_id: String, output: CallOutput },
+}
+
+struct CallOutput {
+    text: String,
+    exit_code: Option<i32>,
+    duration_seconds: Option<f64>,
+    success: Option<bool>,
+}
+
+impl CallOutput {
+    fn parse(output: &Value) -> Self {
+        let mut result = CallOutput {
+            text: String::new(),
+            exit_code: None,
+            duration_seconds: None,
+            success: None,
+        };
+
+        match output {
+            // Shell calls report `{"output": ..., "metadata": {"exit_code": ...}}` as a string
+            Value::String(raw) => match serde_json::from_str::<Value>(raw) {
+                Ok(parsed) if parsed["output"].is_string() => {
+                    result.text = parsed["output"].as_str().unwrap_or_default().to_string();
+                    result.exit_code = parsed["metadata"]["exit_code"].as_i64().map(|c| c as i32);
+                    result.duration_seconds = parsed["metadata"]["duration_seconds"].as_f64();
+                }
+                _ => {
+                    result.text = raw.clone();
+                    // Newer versions format it as text instead
+                    result.exit_code = raw
+                        .strip_prefix("Exit code: ")
+                        .and_then(|rest| rest.lines().next())
+                        .and_then(|code| code.trim().parse().ok());
+                }
+            },
+            other => {
+                result.text = other["content"].as_str().unwrap_or_default().to_string();
+                result.success = other["success"].as_bool();
+            }
+        }
+
+        if result.text.chars().count() > MAX_OUTPUT_CHARS {
+            let mut cut: String = result.text.chars().take(MAX_OUTPUT_CHARS).collect();
+            cut.push_str("\n…");
+            result.text = cut;
+        }
+        result.success = result.success.or(result.exit_code.map(|code| code == 0));
+        result
+    }
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn patch_item(call_id: String, patch: String) -> TurnItem {
+    TurnItem::Patch {
+        call_id,
+        files: patched_files(&patch),
+        patch,
+        output: None,
+        success: None,
+    }
+}
+
+/// Shell commands that run `apply_patch` are shown as the patch they apply.
+fn command_item(call_id: String, command: Vec<String>) -> TurnItem {
+    let script = command.join(" ");
+    let patch = script.find("*** Begin Patch").map(|start| {
+        let rest = &script[start..];
+        match rest.find("*** End Patch") {
+            Some(end) => &rest[..end + "*** End Patch".len()],
+            None => rest,
+        }
+    });
+    if let (Some(patch), true) = (patch, script.contains("apply_patch")) {
+        return patch_item(call_id, patch.to_string());
+    }
+
+    TurnItem::Exec {
+        call_id,
+        command,
+        output: None,
+        exit_code: None,
+        duration_seconds: None,
+    }
+}
+
+fn function_call_item(item: &Value) -> Option<TurnItem> {
+    let name = item["name"].as_str()?;
+    let call_id = item["call_id"].as_str().unwrap_or_default().to_string();
+    let arguments = item["arguments"].as_str().unwrap_or_default();
+    let args: Value = serde_json::from_str(arguments).unwrap_or_default();
+
+    let item = match name {
+        "shell" | "container.exec" => command_item(call_id, string_list(&args["command"])),
+        "apply_patch" => patch_item(call_id, args["input"].as_str()?.to_string()),
+        // MCP tools are exposed to the model as `server__tool`
+        _ => match name.split_once("__") {
+            Some((server, tool)) => TurnItem::McpToolCall {
+                call_id,
+                server: server.to_string(),
+                tool: tool.to_string(),
+                arguments: args,
+                output: None,
+                success: None,
+            },
+            None => TurnItem::ToolCall {
+                call_id,
+                name: name.to_string(),
+                arguments: arguments.to_string(),
+                output: None,
+            },
+        },
+    };
+    Some(item)
+}
+
+fn item_line(item: &Value) -> Option<ItemLine> {
+    let call_id = || {
+        item["call_id"]
+            .as_str()
+            .or_else(|| item["id"].as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    let item = match item["type"].as_str()? {
+        "reasoning" => {
+            let summary = item["summary"]
+                .as_array()?
+                .iter()
+                .filter_map(|part| part["text"].as_str())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            if summary.trim().is_empty() {
+                return None;
+            }
+            TurnItem::Reasoning { summary }
+        }
+        "function_call" => function_call_item(item)?,
+        "local_shell_call" => command_item(call_id(), string_list(&item["action"]["command"])),
+        "custom_tool_call" => {
+            let input = item["input"].as_str().unwrap_or_default().to_string();
+            match item["name"].as_str()? {
+                "apply_patch" => patch_item(call_id(), input),
+                name => TurnItem::ToolCall {
+                    call_id: call_id(),
+                    name: name.to_string(),
+                    arguments: input,
+                    output: None,
+                },
+            }
+        }
+        "function_call_output" | "custom_tool_call_output" => {
+            return Some(ItemLine::Output {
+                call_id: call_id(),
+                output: CallOutput::parse(&item["output"]),
+            });
+        }
+        _ => return None,
+    };
+    Some(ItemLine::Item(item))
+}
+
+/// Fill in the result of the call `call_id` among `items`.
+fn finish_call(items: &mut [TurnItem], call_id: &str, result: CallOutput) {
+    let Some(item) = items.iter_mut().rev().find(|item| match item {
+        TurnItem::Exec { call_id: id, .. }
+        | TurnItem::Patch { call_id: id, .. }
+        | TurnItem::McpToolCall { call_id: id, .. }
+        | TurnItem::ToolCall { call_id: id, .. } => id == call_id,
+        TurnItem::Reasoning { .. } => false,
+    }) else {
+        return;
+    };
+
+    match item {
+        TurnItem::Exec {
+            output,
+            exit_code,
+            duration_seconds,
+            ..
+        } => {
+            *output = Some(result.text);
+            *exit_code = result.exit_code;
+            *duration_seconds = result.duration_seconds;
+        }
+        TurnItem::Patch {
+            output, success, ..
+        }
+        | TurnItem::McpToolCall {
+            output, success, ..
+        } => {
+            *output = Some(result.text);
+            *success = result.success;
+        }
+        TurnItem::ToolCall { output, .. } => *output = Some(result.text),
+        TurnItem::Reasoning { .. } => {}
+    }
+}
+
 pub fn parse_session_file(content: &str, file_path: &Path) -> Option<Conversation> {
     let lines: Vec<&str> = content.trim().lines().collect();
     if lines.is_empty() {
@@ -45,83 +434,60 @@ pub fn parse_session_file(content: &str, file_path: &Path) -> Option<Conversatio
 
     let mut session_id = None;
     let mut session_timestamp = None;
-    let mut messages = Vec::new();
+    let mut messages: Vec<ChatMessage> = Vec::new();
+    // Items recorded before the first message
+    let mut leading_items = Vec::new();
 
     for line in &lines {
-        if let Ok(record) = serde_json::from_str::<SessionRecord>(line) {
+        let Some(record) = unwrap_line(line) else {
+            continue;
+        };
+        if let Some(item_line) = item_line(&record) {
+            let items = match messages.last_mut() {
+                Some(message) => &mut message.items,
+                None => &mut leading_items,
+            };
+            match item_line {
+                ItemLine::Item(item) => items.push(item),
+                ItemLine::Output { call_id, output } => finish_call(items, &call_id, output),
+            }
+            continue;
+        }
+
+        if let Ok(record) = serde_json::from_value::<SessionRecord>(record) {
             // Get session metadata
             if record.id.is_some() && record.timestamp.is_some() {
-                session_id = record.id;
-                session_timestamp = record.timestamp;
+                session_id = record.id.clone();
+                session_timestamp = record.timestamp.clone();
             }
 
-            // Parse messages (check for "type": "message")
-            if record.message_type.as_deref() == Some("message")
-                && record.role.is_some()
-                && record.content.is_some()
-            {
-                let role = record.role.unwrap();
-                let content_value = record.content.unwrap();
-
-                let content_text = if let Some(array) = content_value.as_array() {
-                    array
-                        .iter()
-                        .filter_map(|item| {
-                            if let Some(obj) = item.as_object() {
-                                if let Some(text) = obj.get("text") {
-                                    text.as_str()
-                                } else {
-                                    None
-                                }
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                        .join("")
-                } else if let Some(text) = content_value.as_str() {
-                    text.to_string()
+            if let Some((role, content_text)) = record_message(record) {
+                // Don't filter environment_context messages - let frontend handle them
+
+                let timestamp = if let Some(ts) = &session_timestamp {
+                    chrono::DateTime::parse_from_rfc3339(ts)
+                        .map(|dt| dt.timestamp_millis())
+                        .unwrap_or_else(|_| chrono::Utc::now().timestamp_millis())
                 } else {
-                    String::new()
+                    chrono::Utc::now().timestamp_millis()
                 };
 
-                if !content_text.trim().is_empty() {
-                    // Don't filter environment_context messages - let frontend handle them
-
-                    let timestamp = if let Some(ts) = &session_timestamp {
-                        chrono::DateTime::parse_from_rfc3339(ts)
-                            .map(|dt| dt.timestamp_millis())
-                            .unwrap_or_else(|_| chrono::Utc::now().timestamp_millis())
-                    } else {
-                        chrono::Utc::now().timestamp_millis()
-                    };
-
-                    messages.push(ChatMessage {
-                        id: format!(
-                            "{}-{}-{}",
-                            session_id.as_ref().unwrap_or(&"unknown".to_string()),
-                            role,
-                            timestamp
-                        ),
+                messages.push(ChatMessage {
+                    id: format!(
+                        "{}-{}-{}",
+                        session_id.as_ref().unwrap_or(&"unknown".to_string()),
                         role,
-                        content: content_text.trim().to_string(),
-                        timestamp,
-                    });
-                }
+                        timestamp
+                    ),
+                    role,
+                    content: content_text.trim().to_string(),
+                    timestamp,
+                    items: std::mem::take(&mut leading_items),
+                });
             }
         }
     }
 
-    // If we only have metadata (one line) and no messages, delete the file
-    if lines.len() == 1 && messages.is_empty() && session_id.is_some() {
-        if let Err(e) = fs::remove_file(file_path) {
-            eprintln!("Failed to delete metadata-only file {:?}: {}", file_path, e);
-        } else {
-            println!("Deleted metadata-only session file: {:?}", file_path);
-        }
-        return None;
-    }
-
     if let (Some(id), Some(timestamp_str)) = (session_id, session_timestamp) {
         if !messages.is_empty() {
             let timestamp = chrono::DateTime::parse_from_rfc3339(&timestamp_str)
@@ -169,93 +535,442 @@ pub fn parse_session_file(content: &str, file_path: &Path) -> Option<Conversatio
     None
 }
 
-pub async fn load_sessions_from_disk() -> Result<Vec<Conversation>, String> {
+/// The lines of a rollout up to and including message `message_index`
+/// (numbered like `Conversation.messages`), with the session id and start
+/// time in its metadata replaced.
+fn fork_rollout(
+    content: &str,
+    message_index: usize,
+    new_id: &str,
+    timestamp: &str,
+) -> Result<String, String> {
+    let mut lines = content.trim().lines();
+    let first_line = lines.next().ok_or("Session file is empty")?;
+
+    let mut meta: serde_json::Value = serde_json::from_str(first_line)
+        .map_err(|e| format!("Failed to parse session metadata: {}", e))?;
+    if meta.get("timestamp").is_some() {
+        meta["timestamp"] = timestamp.into();
+    }
+    let meta_fields = if meta.get("id").is_some() {
+        &mut meta
+    } else {
+        meta.get_mut("payload")
+            .filter(|payload| payload.get("id").is_some())
+            .ok_or("Session file has no session id")?
+    };
+    meta_fields["id"] = new_id.into();
+    meta_fields["timestamp"] = timestamp.into();
+
+    let mut forked = vec![meta.to_string()];
+    let mut message_count = 0;
+    for line in lines {
+        forked.push(line.to_string());
+        if line_message(line).is_some() {
+            message_count += 1;
+            if message_count > message_index {
+                break;
+            }
+        }
+    }
+    if message_count <= message_index {
+        return Err(format!(
+            "Message index {} is out of range ({} messages)",
+            message_index, message_count
+        ));
+    }
+
+    Ok(forked.join("\n") + "\n")
+}
+
+/// Copy a rollout up to and including message `message_index` (numbered like
+/// `Conversation.messages`) into a new session file with a fresh id.
+pub async fn fork_session_file(
+    file_path: String,
+    message_index: usize,
+) -> Result<Conversation, String> {
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read session file: {}", e))?;
+    let new_id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Local::now();
+    let timestamp = now
+        .with_timezone(&chrono::Utc)
+        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let forked_content = fork_rollout(&content, message_index, &new_id, &timestamp)?;
+
+    // Same layout codex uses for its own rollouts
     let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
-    let sessions_path = home_dir.join(".codex").join("sessions");
+    let dir = home_dir
+        .join(".codex")
+        .join("sessions")
+        .join(now.format("%Y").to_string())
+        .join(now.format("%m").to_string())
+        .join(now.format("%d").to_string());
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create sessions directory: {}", e))?;
+    let path = dir.join(format!(
+        "rollout-{}-{}.jsonl",
+        now.format("%Y-%m-%dT%H-%M-%S"),
+        new_id
+    ));
+
+    fs::write(&path, &forked_content)
+        .map_err(|e| format!("Failed to write session file: {}", e))?;
+
+    parse_session_file(&forked_content, &path)
+        .ok_or_else(|| "Failed to parse forked session".to_string())
+}
+
+pub async fn delete_session_file(file_path: String) -> Result<(), String> {
+    fs::remove_file(&file_path).map_err(|e| format!("Failed to delete file '{}': {}", file_path, e))
+}
+
+/// Rollout files `clean_up_sessions` moved to the trash, or would move on a
+/// dry run.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionCleanup {
+    pub dry_run: bool,
+    pub files: Vec<String>,
+    /// Metadata-only files left alone because they changed too recently
+    pub skipped_recent: usize,
+    /// Files that could not be read or moved; the rest are still processed
+    pub errors: Vec<CleanupError>,
+    pub trash_dir: String,
+}
 
-    if !sessions_path.exists() {
-        return Ok(Vec::new());
+#[derive(Debug, Serialize)]
+pub struct CleanupError {
+    pub file: String,
+    pub error: String,
+}
+
+/// A rollout holding nothing but its session metadata line.
+fn is_metadata_only(content: &str) -> bool {
+    let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+    lines
+        .next()
+        .is_some_and(|first| rollout_session_id(first).is_some())
+        && lines.next().is_none()
+}
+
+/// Where `path` goes in the trash. Earlier entries with the same name are kept
+/// by numbering the new one.
+fn trash_target(path: &Path, sessions_dir: &Path, trash_dir: &Path) -> Result<PathBuf, String> {
+    let relative = path
+        .strip_prefix(sessions_dir)
+        .map_err(|e| format!("Not in the sessions directory: {}", e))?;
+    let target = trash_dir.join(relative);
+    if !target.exists() {
+        return Ok(target);
     }
 
-    let mut conversations = Vec::new();
+    let stem = target
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    (1..)
+        .map(|n| target.with_file_name(format!("{}.{}.jsonl", stem, n)))
+        .find(|candidate| !candidate.exists())
+        .ok_or_else(|| "No free name in trash".to_string())
+}
+
+fn move_to_trash(path: &Path, sessions_dir: &Path, trash_dir: &Path) -> Result<(), String> {
+    let target = trash_target(path, sessions_dir, trash_dir)?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create trash directory: {}", e))?;
+    }
+    fs::rename(path, &target).map_err(|e| format!("Failed to move to trash: {}", e))
+}
 
-    for entry in WalkDir::new(&sessions_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
-    {
-        match fs::read_to_string(entry.path()) {
-            Ok(content) => {
-                if let Some(conversation) = parse_session_file(&content, entry.path()) {
-                    conversations.push(conversation);
-                }
-            }
+fn clean_up_dir(
+    sessions_dir: &Path,
+    trash_dir: &Path,
+    dry_run: bool,
+    min_age_ms: i64,
+    now: i64,
+) -> SessionCleanup {
+    let mut cleanup = SessionCleanup {
+        dry_run,
+        files: Vec::new(),
+        skipped_recent: 0,
+        errors: Vec::new(),
+        trash_dir: trash_dir.to_string_lossy().to_string(),
+    };
+    for file in rollout_files(sessions_dir) {
+        let path = file.path.to_string_lossy().to_string();
+        let content = match fs::read_to_string(&file.path) {
+            Ok(content) => content,
             Err(e) => {
-                eprintln!("Error reading file {:?}: {}", entry.path(), e);
+                cleanup.errors.push(CleanupError {
+                    file: path,
+                    error: format!("Failed to read session file: {}", e),
+                });
+                continue;
             }
+        };
+        if !is_metadata_only(&content) {
+            continue;
+        }
+        if now - file.mtime < min_age_ms {
+            cleanup.skipped_recent += 1;
+            continue;
         }
-    }
-
-    // Sort by updated_at (newest first)
-    conversations.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
 
-    println!("Total conversations loaded: {}", conversations.len());
+        if !dry_run {
+            if let Err(error) = move_to_trash(&file.path, sessions_dir, trash_dir) {
+                log::warn!("Failed to clean up session file {}: {}", path, error);
+                cleanup.errors.push(CleanupError { file: path, error });
+                continue;
+            }
+            log::info!("Moved metadata-only session file to trash: {}", path);
+        }
+        cleanup.files.push(path);
+    }
 
-    Ok(conversations)
+    cleanup
 }
 
-pub async fn delete_session_file(file_path: String) -> Result<(), String> {
-    fs::remove_file(&file_path).map_err(|e| format!("Failed to delete file '{}': {}", file_path, e))
+/// Move rollouts that hold only session metadata to `~/.codex/trash/sessions`,
+/// keeping their path relative to the sessions directory. Files modified in
+/// the last `min_age_minutes` are skipped.
+pub async fn clean_up_sessions(
+    dry_run: bool,
+    min_age_minutes: Option<u64>,
+) -> Result<SessionCleanup, String> {
+    let sessions_dir = sessions_dir()?;
+    let trash_dir = sessions_dir
+        .parent()
+        .ok_or("Could not find codex directory")?
+        .join("trash")
+        .join("sessions");
+    let min_age_ms =
+        (min_age_minutes.unwrap_or(DEFAULT_CLEANUP_MIN_AGE_MINUTES) * 60 * 1000) as i64;
+    let now = chrono::Utc::now().timestamp_millis();
+
+    Ok(clean_up_dir(
+        &sessions_dir,
+        &trash_dir,
+        dry_run,
+        min_age_ms,
+        now,
+    ))
 }
 
-pub async fn get_latest_session_id() -> Result<Option<String>, String> {
-    let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
-    let sessions_path = home_dir.join(".codex").join("sessions");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEGACY: &str = include_str!("../../tests/fixtures/rollouts/legacy.jsonl");
+    const SESSION_META: &str = include_str!("../../tests/fixtures/rollouts/session_meta.jsonl");
+
+    fn parse(content: &str) -> Conversation {
+        parse_session_file(content, Path::new("rollout.jsonl")).expect("conversation")
+    }
 
-    if !sessions_path.exists() {
-        return Ok(None);
+    #[test]
+    fn parses_legacy_rollouts() {
+        let conversation = parse(LEGACY);
+        assert_eq!(
+            conversation.id,
+            "codex-event-0b6e2a9c-3f41-4c1e-9d7a-2c5e8f1a4b10"
+        );
+        assert_eq!(conversation.title, "Why does the build fail?");
+        let roles: Vec<_> = conversation
+            .messages
+            .iter()
+            .map(|m| m.role.as_str())
+            .collect();
+        assert_eq!(roles, ["user", "assistant", "user", "assistant"]);
+
+        let items = &conversation.messages[0].items;
+        assert!(
+            matches!(&items[0], TurnItem::Reasoning { summary } if summary.contains("build output"))
+        );
+        assert!(matches!(
+            &items[1],
+            TurnItem::Exec { command, exit_code: Some(101), .. } if command == &["cargo", "build"]
+        ));
+        assert!(matches!(
+            &conversation.messages[2].items[0],
+            TurnItem::Patch { files, success: Some(true), .. } if files == &["src/main.rs"]
+        ));
     }
 
-    let mut latest_file: Option<(std::path::PathBuf, std::time::SystemTime)> = None;
+    #[test]
+    fn parses_session_meta_rollouts() {
+        let conversation = parse(SESSION_META);
+        assert_eq!(
+            conversation.id,
+            "codex-event-7d3c9f20-58b1-4e2a-a6f4-91c0d2e3b745"
+        );
+        let roles: Vec<_> = conversation
+            .messages
+            .iter()
+            .map(|m| m.role.as_str())
+            .collect();
+        assert_eq!(roles, ["user", "user", "assistant", "user", "assistant"]);
+        assert_eq!(conversation.messages[1].content, "What is in src?");
+
+        let items = &conversation.messages[1].items;
+        assert_eq!(items.len(), 2);
+        assert!(matches!(&items[0], TurnItem::Reasoning { .. }));
+        assert!(matches!(
+            &items[1],
+            TurnItem::Exec { output: Some(output), exit_code: Some(0), .. } if output.contains("main.rs")
+        ));
+    }
 
-    // Find the most recently modified .jsonl file
-    for entry in WalkDir::new(&sessions_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
-    {
-        if let Ok(metadata) = entry.metadata() {
-            if let Ok(modified) = metadata.modified() {
-                match &latest_file {
-                    None => latest_file = Some((entry.path().to_path_buf(), modified)),
-                    Some((_, latest_time)) => {
-                        if modified > *latest_time {
-                            latest_file = Some((entry.path().to_path_buf(), modified));
-                        }
-                    }
-                }
+    const FORK_ID: &str = "11111111-2222-3333-4444-555555555555";
+    const FORK_TIME: &str = "2026-01-02T03:04:05.000Z";
+
+    #[test]
+    fn forks_legacy_rollouts_at_message() {
+        let forked = fork_rollout(LEGACY, 1, FORK_ID, FORK_TIME).unwrap();
+        let meta: Value = serde_json::from_str(forked.lines().next().unwrap()).unwrap();
+        assert_eq!(meta["id"], FORK_ID);
+        assert_eq!(meta["timestamp"], FORK_TIME);
+
+        let conversation = parse(&forked);
+        assert_eq!(conversation.id, format!("codex-event-{}", FORK_ID));
+        assert_eq!(conversation.messages.len(), 2);
+        assert_eq!(
+            conversation.messages[1].content,
+            "`x` is undefined in main.rs."
+        );
+        // Tool calls before the cut are kept
+        assert_eq!(conversation.messages[0].items.len(), 2);
+    }
+
+    #[test]
+    fn forks_session_meta_rollouts_at_message() {
+        let forked = fork_rollout(SESSION_META, 2, FORK_ID, FORK_TIME).unwrap();
+        let meta: Value = serde_json::from_str(forked.lines().next().unwrap()).unwrap();
+        assert_eq!(meta["payload"]["id"], FORK_ID);
+        assert_eq!(meta["payload"]["timestamp"], FORK_TIME);
+        assert_eq!(meta["timestamp"], FORK_TIME);
+
+        let conversation = parse(&forked);
+        assert_eq!(conversation.id, format!("codex-event-{}", FORK_ID));
+        assert_eq!(conversation.messages.len(), 3);
+        assert_eq!(
+            conversation.messages[2].content,
+            "`src` holds lib.rs and main.rs."
+        );
+        assert!(!forked.contains("Open main.rs"));
+    }
+
+    #[test]
+    fn fork_rejects_out_of_range_index() {
+        assert!(fork_rollout(LEGACY, 4, FORK_ID, FORK_TIME).is_err());
+        assert!(fork_rollout(SESSION_META, 5, FORK_ID, FORK_TIME).is_err());
+    }
+
+    #[test]
+    fn line_message_reads_both_formats() {
+        let legacy = LEGACY.lines().filter_map(line_message).count();
+        let current = SESSION_META.lines().filter_map(line_message).count();
+        assert_eq!((legacy, current), (4, 5));
+    }
+
+    #[test]
+    fn metadata_only_means_a_single_meta_line() {
+        let legacy_meta = LEGACY.lines().next().unwrap();
+        let current_meta = SESSION_META.lines().next().unwrap();
+        assert!(is_metadata_only(legacy_meta));
+        assert!(is_metadata_only(&format!("{}\n\n", current_meta)));
+        assert!(!is_metadata_only(LEGACY));
+        assert!(!is_metadata_only(""));
+        assert!(!is_metadata_only("{\"type\":\"message\"}"));
+    }
+
+    struct CleanupDirs {
+        root: PathBuf,
+        sessions: PathBuf,
+        trash: PathBuf,
+    }
+
+    impl CleanupDirs {
+        fn new() -> Self {
+            let root =
+                std::env::temp_dir().join(format!("codexia-cleanup-{}", uuid::Uuid::new_v4()));
+            let sessions = root.join("sessions");
+            fs::create_dir_all(sessions.join("2025/09/02")).unwrap();
+            let trash = root.join("trash");
+            Self {
+                root,
+                sessions,
+                trash,
             }
         }
+
+        fn write(&self, name: &str, content: &str) -> PathBuf {
+            let path = self.sessions.join("2025/09/02").join(name);
+            fs::write(&path, content).unwrap();
+            path
+        }
     }
 
-    if let Some((file_path, _)) = latest_file {
-        // Read the first line to get session ID
-        if let Ok(content) = fs::read_to_string(&file_path) {
-            let first_line = content.lines().next().unwrap_or("");
-            if let Ok(record) = serde_json::from_str::<SessionRecord>(first_line) {
-                if let Some(id) = record.id {
-                    let full_session_id = if id.starts_with("codex-event-") {
-                        id
-                    } else {
-                        format!("codex-event-{}", id)
-                    };
-                    return Ok(Some(full_session_id));
-                }
-            }
+    impl Drop for CleanupDirs {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
         }
     }
 
-    Ok(None)
+    fn mtime(path: &Path) -> i64 {
+        fs::metadata(path)
+            .unwrap()
+            .modified()
+            .unwrap()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64
+    }
+
+    #[test]
+    fn cleanup_dry_run_moves_nothing() {
+        let dirs = CleanupDirs::new();
+        let empty = dirs.write("empty.jsonl", LEGACY.lines().next().unwrap());
+        dirs.write("full.jsonl", LEGACY);
+
+        let cleanup = clean_up_dir(&dirs.sessions, &dirs.trash, true, 0, mtime(&empty) + 1);
+        assert_eq!(cleanup.files, [empty.to_string_lossy()]);
+        assert!(cleanup.errors.is_empty());
+        assert!(empty.exists());
+        assert!(!dirs.trash.exists());
+    }
+
+    #[test]
+    fn cleanup_skips_recent_files_and_keeps_trash_entries() {
+        let dirs = CleanupDirs::new();
+        let empty = dirs.write("empty.jsonl", LEGACY.lines().next().unwrap());
+        let min_age_ms = 10 * 60 * 1000;
+
+        let cleanup = clean_up_dir(
+            &dirs.sessions,
+            &dirs.trash,
+            false,
+            min_age_ms,
+            mtime(&empty),
+        );
+        assert_eq!((cleanup.files.len(), cleanup.skipped_recent), (0, 1));
+        assert!(empty.exists());
+
+        let later = mtime(&empty) + min_age_ms;
+        let cleanup = clean_up_dir(&dirs.sessions, &dirs.trash, false, min_age_ms, later);
+        assert_eq!((cleanup.files.len(), cleanup.skipped_recent), (1, 0));
+        let trashed = dirs.trash.join("2025/09/02/empty.jsonl");
+        assert!(!empty.exists() && trashed.exists());
+
+        // A second file with the same name does not replace the first
+        let again = dirs.write("empty.jsonl", SESSION_META.lines().next().unwrap());
+        let cleanup = clean_up_dir(&dirs.sessions, &dirs.trash, false, 0, mtime(&again) + 1);
+        assert_eq!(cleanup.files.len(), 1);
+        assert!(cleanup.errors.is_empty());
+        assert_eq!(
+            fs::read_to_string(&trashed).unwrap(),
+            LEGACY.lines().next().unwrap()
+        );
+        assert!(dirs.trash.join("2025/09/02/empty.1.jsonl").exists());
+    }
 }
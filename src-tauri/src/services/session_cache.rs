@@ -0,0 +1,197 @@
+use super::session::{self, Conversation};
+use anyhow::{anyhow, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+// Bump when `Conversation` changes shape so stale entries get reparsed
+const CACHE_VERSION: i64 = 2;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS sessions (
+    path TEXT PRIMARY KEY,
+    mtime INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    session_id TEXT,
+    updated_at INTEGER,
+    conversation TEXT
+);
+CREATE INDEX IF NOT EXISTS sessions_by_update ON sessions (updated_at DESC);
+";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionPage {
+    pub conversations: Vec<Conversation>,
+    /// Conversations in the cache, across all pages
+    pub total: usize,
+    /// Offset of the next page, if there is one
+    pub next_offset: Option<usize>,
+}
+
+fn cache_path(app: &AppHandle) -> Result<PathBuf> {
+    let dir = app.path().app_data_dir()?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join("session_cache.sqlite"))
+}
+
+fn open_cache(path: &Path) -> Result<Connection> {
+    let conn = Connection::open(path)?;
+    conn.busy_timeout(Duration::from_secs(10))?;
+
+    let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version != CACHE_VERSION {
+        conn.execute_batch("DROP TABLE IF EXISTS sessions;")?;
+        conn.pragma_update(None, "user_version", CACHE_VERSION)?;
+    }
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+/// Reparse rollout files that are new or whose mtime or size changed, and
+/// forget the ones that are gone. Files that do not hold a conversation are
+/// cached too, so they are not read again on every load.
+fn sync_cache(conn: &mut Connection) -> Result<()> {
+    let sessions_dir = session::sessions_dir().map_err(|e| anyhow!(e))?;
+    let known: HashMap<String, (i64, i64)> = {
+        let mut stmt = conn.prepare("SELECT path, mtime, size FROM sessions")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?;
+        rows.collect::<rusqlite::Result<_>>()?
+    };
+
+    let tx = conn.transaction()?;
+    let mut seen = HashSet::new();
+    let mut parsed = 0;
+    for file in session::rollout_files(&sessions_dir) {
+        let Some(path) = file.path.to_str().map(str::to_string) else {
+            continue;
+        };
+        seen.insert(path.clone());
+        if known.get(&path) == Some(&(file.mtime, file.size)) {
+            continue;
+        }
+        let content = match fs::read_to_string(&file.path) {
+            Ok(content) => content,
+            Err(e) => {
+                log::warn!("Error reading file {:?}: {}", file.path, e);
+                continue;
+            }
+        };
+
+        let session_id =
+            session::rollout_session_id(&content).map(|id| format!("codex-event-{}", id));
+        let conversation = session::parse_session_file(&content, &file.path);
+        tx.execute(
+            "INSERT OR REPLACE INTO sessions (path, mtime, size, session_id, updated_at, conversation)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                path,
+                file.mtime,
+                file.size,
+                session_id,
+                conversation.as_ref().map(|c| c.updated_at),
+                conversation
+                    .as_ref()
+                    .map(serde_json::to_string)
+                    .transpose()?,
+            ],
+        )?;
+        parsed += 1;
+    }
+
+    for path in known.keys().filter(|path| !seen.contains(*path)) {
+        tx.execute("DELETE FROM sessions WHERE path = ?1", [path])?;
+    }
+    tx.commit()?;
+
+    if parsed > 0 {
+        log::info!("Session cache: parsed {} new or changed files", parsed);
+    }
+    Ok(())
+}
+
+fn read_page(conn: &Connection, offset: usize, limit: Option<usize>) -> Result<SessionPage> {
+    let total: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sessions WHERE conversation IS NOT NULL",
+        [],
+        |row| row.get(0),
+    )?;
+    let total = total as usize;
+
+    // A negative LIMIT means no limit in SQLite
+    let limit_param = limit.map(|l| l as i64).unwrap_or(-1);
+    let mut stmt = conn.prepare(
+        "SELECT conversation FROM sessions WHERE conversation IS NOT NULL
+         ORDER BY updated_at DESC, path DESC LIMIT ?1 OFFSET ?2",
+    )?;
+    let rows = stmt.query_map(params![limit_param, offset as i64], |row| {
+        row.get::<_, String>(0)
+    })?;
+    let mut conversations = Vec::new();
+    for json in rows {
+        conversations.push(serde_json::from_str(&json?)?);
+    }
+
+    let end = offset + conversations.len();
+    Ok(SessionPage {
+        conversations,
+        total,
+        next_offset: (end < total).then_some(end),
+    })
+}
+
+async fn with_cache<T, F>(app: &AppHandle, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+{
+    let path = cache_path(app).map_err(|e| format!("Failed to open session cache: {}", e))?;
+    tokio::task::spawn_blocking(move || f(&mut open_cache(&path)?))
+        .await
+        .map_err(|e| format!("Failed to load sessions: {}", e))?
+        .map_err(|e| format!("Failed to load sessions: {}", e))
+}
+
+/// One page of past conversations, newest first. The first page brings the
+/// cache up to date; later pages read from it as is.
+pub async fn load_sessions_page(
+    app: AppHandle,
+    offset: usize,
+    limit: usize,
+) -> Result<SessionPage, String> {
+    with_cache(&app, move |conn| {
+        if offset == 0 {
+            sync_cache(conn)?;
+        }
+        read_page(conn, offset, Some(limit))
+    })
+    .await
+}
+
+pub async fn load_sessions_from_disk(app: AppHandle) -> Result<Vec<Conversation>, String> {
+    with_cache(&app, |conn| {
+        sync_cache(conn)?;
+        Ok(read_page(conn, 0, None)?.conversations)
+    })
+    .await
+}
+
+/// Id of the most recently modified rollout file
+pub async fn get_latest_session_id(app: AppHandle) -> Result<Option<String>, String> {
+    with_cache(&app, |conn| {
+        sync_cache(conn)?;
+        let id = conn
+            .query_row(
+                "SELECT session_id FROM sessions ORDER BY mtime DESC LIMIT 1",
+                [],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()?;
+        Ok(id.flatten())
+    })
+    .await
+}
@@ -1,50 +1,201 @@
 use crate::codex_client::ProcessHandle;
-use crate::protocol::{CodexConfig, ConnectionConfig};
+use crate::protocol::{CodexConfig, ConnectionConfig, SshAuthMode};
 use anyhow::{anyhow, Result};
-#[cfg(target_os = "macos")]
+use std::path::PathBuf;
 use std::process::Stdio;
-#[cfg(target_os = "macos")]
 use std::time::Duration;
-#[cfg(target_os = "macos")]
+use tauri::path::BaseDirectory;
+use tauri::{AppHandle, Manager};
 use tokio::process::Command;
-#[cfg(target_os = "macos")]
 use tokio::time::timeout;
 
 pub struct SshProcess;
 
-impl SshProcess {
+/// Password credentials for `SshAuthMode::Password`. The password is only ever
+/// handed to ssh through the bundled askpass helper's environment.
+pub struct SshCredentials {
+    askpass: PathBuf,
+    password: String,
+}
+
+impl SshCredentials {
+    pub fn new(app: &AppHandle, password: String) -> Result<Self> {
+        let askpass = app
+            .path()
+            .resolve("resources/askpass", BaseDirectory::Resource)?;
+        // The build writes an empty placeholder when the helper could not be compiled
+        if !askpass.metadata().map(|m| m.len() > 0).unwrap_or(false) {
+            return Err(anyhow!("askpass helper not found"));
+        }
+        Ok(Self { askpass, password })
+    }
+
+    /// Resolve credentials for a connection, requiring a password only in password mode.
+    pub fn for_connection(
+        app: &AppHandle,
+        conn: &ConnectionConfig,
+        password: Option<String>,
+    ) -> Result<Option<Self>> {
+        if conn.auth != SshAuthMode::Password {
+            return Ok(None);
+        }
+        match password {
+            Some(p) if !p.is_empty() => Self::new(app, p).map(Some),
+            _ => Err(anyhow!("password required")),
+        }
+    }
+}
+
+/// Locate the ssh client. `CODEXIA_SSH` overrides discovery (useful for shims),
+/// otherwise the first `ssh` on PATH is used.
+pub fn discover_ssh_command() -> Result<PathBuf> {
+    if let Ok(explicit) = std::env::var("CODEXIA_SSH") {
+        let p = PathBuf::from(&explicit);
+        if p.exists() {
+            log::debug!("Using CODEXIA_SSH override at {}", p.display());
+            return Ok(p);
+        }
+        log::warn!("CODEXIA_SSH provided but not found: {}", explicit);
+    }
+    if let Ok(p) = which::which("ssh") {
+        return Ok(p);
+    }
     #[cfg(target_os = "macos")]
-    pub fn spawn(config: &CodexConfig) -> Result<ProcessHandle> {
-        let conn = config
-            .connection
-            .as_ref()
-            .ok_or_else(|| anyhow!("missing connection config"))?;
-        let target = if conn.user.is_empty() {
-            conn.host.clone()
-        } else {
-            format!("{}@{}", conn.user, conn.host)
-        };
-        let mut cmd = Command::new("/usr/bin/ssh");
-        cmd.arg("-T")
-            .arg("-o")
-            .arg("BatchMode=yes")
+    {
+        let p = PathBuf::from("/usr/bin/ssh");
+        if p.exists() {
+            return Ok(p);
+        }
+    }
+    Err(anyhow!("ssh not found"))
+}
+
+/// Quote a string for a POSIX shell. A leading `~/` is left unquoted so the
+/// remote shell still expands it to the remote home directory.
+pub fn shell_quote(s: &str) -> String {
+    if let Some(rest) = s.strip_prefix("~/") {
+        return format!("~/{}", shell_quote(rest));
+    }
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Socket path template for ssh connection sharing. Kept short because unix
+/// sockets are limited to ~104 bytes; `%C` expands to a hash of the target.
+#[cfg(unix)]
+fn control_path() -> Option<PathBuf> {
+    use std::os::unix::fs::DirBuilderExt;
+    let dir = dirs::home_dir()?.join(".ssh");
+    if !dir.exists() {
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&dir)
+            .ok()?;
+    }
+    Some(dir.join("codexia-%C"))
+}
+
+// Windows OpenSSH has no connection sharing
+#[cfg(not(unix))]
+fn control_path() -> Option<PathBuf> {
+    None
+}
+
+fn target(conn: &ConnectionConfig) -> String {
+    if conn.user.is_empty() {
+        conn.host.clone()
+    } else {
+        format!("{}@{}", conn.user, conn.host)
+    }
+}
+
+fn base_command(conn: &ConnectionConfig, creds: Option<&SshCredentials>) -> Result<Command> {
+    let mut cmd = Command::new(discover_ssh_command()?);
+    cmd.arg("-T")
+        .arg("-o")
+        .arg("StrictHostKeyChecking=accept-new")
+        .arg("-o")
+        .arg("ConnectTimeout=5")
+        .arg("-o")
+        .arg("ServerAliveInterval=15")
+        .arg("-o")
+        .arg("ServerAliveCountMax=3");
+    if let Some(control_path) = control_path() {
+        // One master connection per host, shared by sessions, file operations
+        // and connection tests
+        cmd.arg("-o")
+            .arg("ControlMaster=auto")
             .arg("-o")
-            .arg("StrictHostKeyChecking=accept-new")
+            .arg(format!("ControlPath={}", control_path.display()))
             .arg("-o")
-            .arg("ConnectTimeout=5");
-        if let Some(port) = conn.port {
-            cmd.arg("-p").arg(port.to_string());
+            .arg("ControlPersist=10m");
+    }
+    if let Some(port) = conn.port {
+        cmd.arg("-p").arg(port.to_string());
+    }
+    if let Some(jump) = &conn.jump_host {
+        if !jump.is_empty() {
+            cmd.arg("-J").arg(jump);
+        }
+    }
+    match conn.auth {
+        SshAuthMode::Key => {
+            cmd.arg("-o").arg("BatchMode=yes");
+            if let Some(key) = &conn.key_path {
+                if !key.is_empty() {
+                    cmd.arg("-i").arg(key);
+                }
+            }
+        }
+        SshAuthMode::Agent => {
+            cmd.arg("-o")
+                .arg("BatchMode=yes")
+                .arg("-o")
+                .arg("PreferredAuthentications=publickey");
         }
-        if let Some(key) = &conn.key_path {
-            if !key.is_empty() {
-                cmd.arg("-i").arg(key);
+        SshAuthMode::Password => {
+            let creds = creds.ok_or_else(|| anyhow!("password required"))?;
+            // BatchMode would disable askpass, so password mode relies on
+            // SSH_ASKPASS_REQUIRE=force to keep ssh from touching a tty.
+            cmd.arg("-o")
+                .arg("PreferredAuthentications=keyboard-interactive,password")
+                .arg("-o")
+                .arg("PubkeyAuthentication=no")
+                .arg("-o")
+                .arg("NumberOfPasswordPrompts=1")
+                .env("SSH_ASKPASS", &creds.askpass)
+                .env("SSH_ASKPASS_REQUIRE", "force")
+                .env("APP_SSH_PASS", &creds.password);
+            if std::env::var_os("DISPLAY").is_none() {
+                // Older OpenSSH only consults SSH_ASKPASS when DISPLAY is set
+                cmd.env("DISPLAY", ":0");
             }
         }
-        cmd.arg(&target).arg("codex");
+    }
+    Ok(cmd)
+}
+
+impl SshProcess {
+    pub fn spawn(config: &CodexConfig, creds: Option<&SshCredentials>) -> Result<ProcessHandle> {
+        let conn = config
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("missing connection config"))?;
+        let mut cmd = base_command(conn, creds)?;
+        let codex = conn
+            .codex_path
+            .as_deref()
+            .filter(|p| !p.is_empty())
+            .unwrap_or("codex");
+        cmd.arg(target(conn)).arg(shell_quote(codex));
         if let Some(args) = &config.custom_args {
             cmd.args(args);
         }
         cmd.arg("proto");
+        if let Some(resume_path) = &config.resume_path {
+            cmd.arg("-c")
+                .arg(shell_quote(&format!("experimental_resume={}", resume_path)));
+        }
         let mut child = cmd
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
@@ -52,42 +203,38 @@ impl SshProcess {
             .spawn()?;
         let stdin = child.stdin.take().ok_or_else(|| anyhow!("stdin"))?;
         let stdout = child.stdout.take().ok_or_else(|| anyhow!("stdout"))?;
+        let stderr = child.stderr.take().ok_or_else(|| anyhow!("stderr"))?;
         Ok(ProcessHandle {
             child,
             stdin,
             stdout,
+            stderr,
         })
     }
 
-    #[cfg(not(target_os = "macos"))]
-    pub fn spawn(_config: &CodexConfig) -> Result<ProcessHandle> {
-        Err(anyhow!("not yet implemented"))
+    /// Run a POSIX shell script on the remote host and collect its output.
+    pub async fn run_script(
+        conn: &ConnectionConfig,
+        creds: Option<&SshCredentials>,
+        script: &str,
+    ) -> Result<std::process::Output> {
+        let mut cmd = base_command(conn, creds)?;
+        // The remote login shell may not be POSIX, so always hand the script to sh
+        cmd.arg(target(conn))
+            .arg(format!("sh -c {}", shell_quote(script)))
+            .stdin(Stdio::null());
+        match timeout(Duration::from_secs(30), cmd.output()).await {
+            Err(_) => Err(anyhow!("timeout")),
+            Ok(res) => Ok(res?),
+        }
     }
 
-    #[cfg(target_os = "macos")]
-    pub async fn test_connection(conn: &ConnectionConfig) -> Result<String> {
-        let target = if conn.user.is_empty() {
-            conn.host.clone()
-        } else {
-            format!("{}@{}", conn.user, conn.host)
-        };
-        let mut cmd = Command::new("/usr/bin/ssh");
-        cmd.arg("-T")
-            .arg("-o")
-            .arg("BatchMode=yes")
-            .arg("-o")
-            .arg("StrictHostKeyChecking=accept-new")
-            .arg("-o")
-            .arg("ConnectTimeout=5");
-        if let Some(port) = conn.port {
-            cmd.arg("-p").arg(port.to_string());
-        }
-        if let Some(key) = &conn.key_path {
-            if !key.is_empty() {
-                cmd.arg("-i").arg(key);
-            }
-        }
-        cmd.arg(&target).arg("echo").arg("ok");
+    pub async fn test_connection(
+        conn: &ConnectionConfig,
+        creds: Option<&SshCredentials>,
+    ) -> Result<String> {
+        let mut cmd = base_command(conn, creds)?;
+        cmd.arg(target(conn)).arg("echo").arg("ok");
         let output = match timeout(Duration::from_secs(5), cmd.output()).await {
             Err(_) => return Err(anyhow!("timeout")),
             Ok(res) => res.map_err(|e| {
@@ -106,14 +253,12 @@ impl SshProcess {
         }
         let err = String::from_utf8_lossy(&output.stderr).to_lowercase();
         if err.contains("permission denied") {
-            Err(anyhow!("permission denied (publickey)"))
+            match conn.auth {
+                SshAuthMode::Password => Err(anyhow!("permission denied (password)")),
+                _ => Err(anyhow!("permission denied (publickey)")),
+            }
         } else {
             Err(anyhow!("host unreachable"))
         }
     }
-
-    #[cfg(not(target_os = "macos"))]
-    pub async fn test_connection(_conn: &ConnectionConfig) -> Result<String> {
-        Err(anyhow!("not yet implemented"))
-    }
 }
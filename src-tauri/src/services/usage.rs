@@ -0,0 +1,683 @@
+use super::session;
+use crate::protocol::{CodexConfig, TokenUsage, TokenUsageInfo};
+use chrono::{DateTime, Datelike, Duration, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// Sessions append from their own transport tasks
+static USAGE_LOG_LOCK: Mutex<()> = Mutex::new(());
+
+static USAGE_CACHE: Mutex<Option<UsageCache>> = Mutex::new(None);
+
+/// Token usage of one model response, as reported by codex's `token_count` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub session_id: String,
+    pub model: String,
+    pub provider: String,
+    /// Working directory the session ran in, prefixed with `host:` for remote
+    /// sessions
+    pub project: String,
+    pub timestamp: i64,
+    pub usage: TokenUsage,
+}
+
+/// USD per million tokens. Cached input is billed instead of, not on top of,
+/// the regular input price.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    #[serde(default)]
+    pub cached_input: f64,
+    pub output: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PriceTable {
+    #[serde(default)]
+    pub models: HashMap<String, ModelPrice>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageGrouping {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UsageQuery {
+    /// Inclusive lower bound, in milliseconds
+    #[serde(default)]
+    pub from: Option<i64>,
+    /// Exclusive upper bound, in milliseconds
+    #[serde(default)]
+    pub to: Option<i64>,
+    #[serde(default)]
+    pub group_by: UsageGrouping,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UsageTotals {
+    pub input_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub output_tokens: u64,
+    pub reasoning_output_tokens: u64,
+    pub total_tokens: u64,
+    pub cost: f64,
+    pub turns: u64,
+    pub sessions: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageBucket {
+    pub key: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UsageReport {
+    pub totals: UsageTotals,
+    pub by_session: Vec<UsageBucket>,
+    pub by_model: Vec<UsageBucket>,
+    pub by_project: Vec<UsageBucket>,
+    /// Oldest period first
+    pub timeline: Vec<UsageBucket>,
+    /// Models with no entry in the price table, counted at zero cost
+    pub unpriced_models: Vec<String>,
+    /// Rollouts in range that recorded no token usage (codex versions before
+    /// `token_count` was written to rollouts) and are not counted
+    pub untracked_sessions: usize,
+}
+
+fn codex_dir() -> Result<PathBuf, String> {
+    let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
+    Ok(home_dir.join(".codex"))
+}
+
+fn get_usage_log_path() -> Result<PathBuf, String> {
+    Ok(codex_dir()?.join("token_usage.jsonl"))
+}
+
+fn get_price_table_path() -> Result<PathBuf, String> {
+    Ok(codex_dir()?.join("model_prices.toml"))
+}
+
+fn default_prices() -> PriceTable {
+    let prices = [
+        ("gpt-5", 1.25, 0.125, 10.0),
+        ("gpt-5-mini", 0.25, 0.025, 2.0),
+        ("gpt-5-nano", 0.05, 0.005, 0.4),
+        ("gpt-4.1", 2.0, 0.5, 8.0),
+        ("gpt-4.1-mini", 0.4, 0.1, 1.6),
+        ("gpt-4o", 2.5, 1.25, 10.0),
+        ("o3", 2.0, 0.5, 8.0),
+        ("o4-mini", 1.1, 0.275, 4.4),
+        ("codex-mini-latest", 1.5, 0.375, 6.0),
+    ];
+    PriceTable {
+        models: prices
+            .into_iter()
+            .map(|(model, input, cached_input, output)| {
+                (
+                    model.to_string(),
+                    ModelPrice {
+                        input,
+                        cached_input,
+                        output,
+                    },
+                )
+            })
+            .collect(),
+    }
+}
+
+/// Key a session's usage is grouped under. Remote sessions include the host, so
+/// the same path on two machines stays apart.
+pub fn project_key(config: &CodexConfig) -> String {
+    match config
+        .connection
+        .as_ref()
+        .filter(|c| c.connection_type.as_str() == "ssh")
+    {
+        Some(conn) => format!("{}:{}", conn.host, config.working_directory),
+        None => config.working_directory.clone(),
+    }
+}
+
+/// Append one turn's usage to the log. Failures are logged, not surfaced,
+/// since they must not disturb the running session.
+pub fn record_usage(record: &UsageRecord) {
+    let result = (|| -> Result<(), String> {
+        let path = get_usage_log_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let mut line = serde_json::to_string(record).map_err(|e| e.to_string())?;
+        line.push('\n');
+
+        let _guard = USAGE_LOG_LOCK.lock().unwrap();
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| e.to_string())
+    })();
+
+    if let Err(e) = result {
+        log::warn!(
+            "Failed to record token usage for {}: {}",
+            record.session_id,
+            e
+        );
+    }
+}
+
+/// Usage recovered from a rollout's `token_count` events, for sessions that
+/// never ran through Codexia or ran before the usage log existed.
+#[derive(Debug, Default)]
+struct RolloutUsage {
+    session_id: Option<String>,
+    records: Vec<UsageRecord>,
+}
+
+fn line_timestamp(line: &Value) -> Option<i64> {
+    DateTime::parse_from_rfc3339(line["timestamp"].as_str()?)
+        .ok()
+        .map(|t| t.timestamp_millis())
+}
+
+fn rollout_usage(content: &str, mtime: i64) -> RolloutUsage {
+    let mut usage = RolloutUsage {
+        session_id: content.lines().next().and_then(session::rollout_session_id),
+        records: Vec::new(),
+    };
+    let Some(session_id) = usage.session_id.clone() else {
+        return usage;
+    };
+    // Older rollouts have no token counts at all; skip parsing them
+    if !content.contains("token_count") {
+        return usage;
+    }
+
+    let (mut model, mut provider, mut project) = (String::new(), String::new(), String::new());
+    let mut last_total = None;
+    for line in content.lines() {
+        let Ok(line) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let payload = &line["payload"];
+        match (line["type"].as_str(), payload["type"].as_str()) {
+            (Some("session_meta"), _) => {
+                project = payload["cwd"].as_str().unwrap_or_default().to_string();
+                provider = payload["model_provider"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+            }
+            (Some("turn_context"), _) => {
+                if let Some(turn_model) = payload["model"].as_str() {
+                    model = turn_model.to_string();
+                }
+                if let Some(cwd) = payload["cwd"].as_str() {
+                    project = cwd.to_string();
+                }
+            }
+            (Some("event_msg"), Some("token_count")) => {
+                let Ok(info) = serde_json::from_value::<TokenUsageInfo>(payload["info"].clone())
+                else {
+                    continue;
+                };
+                // codex repeats the last count when nothing new was used
+                let total = info.total_token_usage.total_tokens;
+                if last_total == Some(total) {
+                    continue;
+                }
+                last_total = Some(total);
+                usage.records.push(UsageRecord {
+                    session_id: session_id.clone(),
+                    model: model.clone(),
+                    provider: provider.clone(),
+                    project: project.clone(),
+                    timestamp: line_timestamp(&line).unwrap_or(mtime),
+                    usage: info.last_token_usage,
+                });
+            }
+            _ => {}
+        }
+    }
+    usage
+}
+
+/// Parsed usage log and rollouts, so a query only reads what changed since
+/// the last one.
+#[derive(Default)]
+struct UsageCache {
+    log_records: Vec<UsageRecord>,
+    /// Bytes of the usage log already parsed
+    log_offset: u64,
+    /// Keyed by path, with the mtime and size the entry was parsed at
+    rollouts: HashMap<PathBuf, (i64, i64, RolloutUsage)>,
+}
+
+impl UsageCache {
+    /// Parse the lines appended to the usage log since the last call. A
+    /// trailing line without its newline is still being written and is left
+    /// for the next call.
+    fn read_log(&mut self) -> Result<(), String> {
+        let path = get_usage_log_path()?;
+        let Ok(mut file) = File::open(&path) else {
+            self.log_records.clear();
+            self.log_offset = 0;
+            return Ok(());
+        };
+        let len = file
+            .metadata()
+            .map_err(|e| format!("Failed to read usage log: {}", e))?
+            .len();
+        if len < self.log_offset {
+            self.log_records.clear();
+            self.log_offset = 0;
+        }
+
+        let mut appended = Vec::new();
+        file.seek(SeekFrom::Start(self.log_offset))
+            .and_then(|_| file.read_to_end(&mut appended))
+            .map_err(|e| format!("Failed to read usage log: {}", e))?;
+        let Some(end) = appended.iter().rposition(|&b| b == b'\n') else {
+            return Ok(());
+        };
+        self.log_records.extend(
+            String::from_utf8_lossy(&appended[..end])
+                .lines()
+                .filter_map(|line| serde_json::from_str::<UsageRecord>(line).ok()),
+        );
+        self.log_offset += end as u64 + 1;
+        Ok(())
+    }
+
+    fn read_rollouts(&mut self) {
+        let Ok(sessions_dir) = session::sessions_dir() else {
+            return;
+        };
+        let mut seen = HashSet::new();
+        for file in session::rollout_files(&sessions_dir) {
+            seen.insert(file.path.clone());
+            if matches!(self.rollouts.get(&file.path), Some((mtime, size, _)) if (*mtime, *size) == (file.mtime, file.size))
+            {
+                continue;
+            }
+            let usage = match fs::read_to_string(&file.path) {
+                Ok(content) => rollout_usage(&content, file.mtime),
+                Err(e) => {
+                    log::warn!("Error reading file {:?}: {}", file.path, e);
+                    RolloutUsage::default()
+                }
+            };
+            self.rollouts
+                .insert(file.path, (file.mtime, file.size, usage));
+        }
+        self.rollouts.retain(|path, _| seen.contains(path));
+    }
+
+    /// The log's records, plus rollout records for sessions the log does not
+    /// cover, and how many rollouts in range had no usage at all.
+    fn records(&self, query: &UsageQuery) -> (Vec<UsageRecord>, usize) {
+        let logged: HashSet<&str> = self
+            .log_records
+            .iter()
+            .map(|r| r.session_id.as_str())
+            .collect();
+        let mut records = self.log_records.clone();
+        let mut untracked = 0;
+        for (mtime, _, usage) in self.rollouts.values() {
+            let Some(session_id) = &usage.session_id else {
+                continue;
+            };
+            if logged.contains(session_id.as_str()) {
+                continue;
+            }
+            if usage.records.is_empty() {
+                untracked += usize::from(in_range(query, *mtime));
+            }
+            records.extend(usage.records.iter().cloned());
+        }
+        (records, untracked)
+    }
+}
+
+fn load_usage_records(query: &UsageQuery) -> Result<(Vec<UsageRecord>, usize), String> {
+    let mut cache = USAGE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = cache.get_or_insert_with(UsageCache::default);
+    cache.read_log()?;
+    cache.read_rollouts();
+    Ok(cache.records(query))
+}
+
+pub async fn read_model_prices() -> Result<PriceTable, String> {
+    let path = get_price_table_path()?;
+
+    if !path.exists() {
+        return Ok(default_prices());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read model prices: {}", e))?;
+
+    toml::from_str(&content).map_err(|e| format!("Failed to parse model prices: {}", e))
+}
+
+pub async fn save_model_prices(prices: PriceTable) -> Result<(), String> {
+    let path = get_price_table_path()?;
+    let toml_content =
+        toml::to_string(&prices).map_err(|e| format!("Failed to serialize model prices: {}", e))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    fs::write(&path, toml_content).map_err(|e| format!("Failed to write model prices: {}", e))
+}
+
+/// Price for `model`, falling back to the longest listed prefix so dated or
+/// suffixed variants ("gpt-5-codex") use their base model's price.
+fn price_for<'a>(prices: &'a PriceTable, model: &str) -> Option<&'a ModelPrice> {
+    prices.models.get(model).or_else(|| {
+        prices
+            .models
+            .iter()
+            .filter(|(name, _)| model.starts_with(name.as_str()))
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, price)| price)
+    })
+}
+
+fn turn_cost(usage: &TokenUsage, price: &ModelPrice) -> f64 {
+    let cached = usage.cached_input_tokens.min(usage.input_tokens);
+    let uncached = usage.input_tokens - cached;
+    (uncached as f64 * price.input + cached as f64 * price.cached_input
+        // Reasoning tokens are already part of the output count
+        + usage.output_tokens as f64 * price.output)
+        / 1_000_000.0
+}
+
+fn period_key(timestamp: i64, grouping: UsageGrouping) -> String {
+    let date = DateTime::from_timestamp_millis(timestamp)
+        .unwrap_or_default()
+        .with_timezone(&Local)
+        .date_naive();
+    match grouping {
+        UsageGrouping::Day => date.format("%Y-%m-%d").to_string(),
+        UsageGrouping::Week => {
+            let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+            monday.format("%Y-%m-%d").to_string()
+        }
+        UsageGrouping::Month => date.format("%Y-%m").to_string(),
+    }
+}
+
+#[derive(Default)]
+struct Accumulator {
+    totals: UsageTotals,
+    sessions: HashSet<String>,
+}
+
+impl Accumulator {
+    fn add(&mut self, record: &UsageRecord, cost: f64) {
+        let usage = &record.usage;
+        self.totals.input_tokens += usage.input_tokens;
+        self.totals.cached_input_tokens += usage.cached_input_tokens;
+        self.totals.output_tokens += usage.output_tokens;
+        self.totals.reasoning_output_tokens += usage.reasoning_output_tokens;
+        self.totals.total_tokens += usage.total_tokens;
+        self.totals.cost += cost;
+        self.totals.turns += 1;
+        self.sessions.insert(record.session_id.clone());
+    }
+
+    fn finish(mut self) -> UsageTotals {
+        self.totals.sessions = self.sessions.len();
+        self.totals
+    }
+}
+
+fn into_buckets(groups: BTreeMap<String, Accumulator>) -> Vec<UsageBucket> {
+    groups
+        .into_iter()
+        .map(|(key, acc)| UsageBucket {
+            key,
+            totals: acc.finish(),
+        })
+        .collect()
+}
+
+fn by_cost(mut buckets: Vec<UsageBucket>) -> Vec<UsageBucket> {
+    buckets.sort_by(|a, b| b.totals.cost.total_cmp(&a.totals.cost));
+    buckets
+}
+
+fn in_range(query: &UsageQuery, timestamp: i64) -> bool {
+    query.from.is_none_or(|from| timestamp >= from) && query.to.is_none_or(|to| timestamp < to)
+}
+
+fn aggregate_usage(
+    records: &[UsageRecord],
+    prices: &PriceTable,
+    query: &UsageQuery,
+) -> UsageReport {
+    let mut totals = Accumulator::default();
+    let mut sessions: BTreeMap<String, Accumulator> = BTreeMap::new();
+    let mut models: BTreeMap<String, Accumulator> = BTreeMap::new();
+    let mut projects: BTreeMap<String, Accumulator> = BTreeMap::new();
+    let mut timeline: BTreeMap<String, Accumulator> = BTreeMap::new();
+    let mut unpriced = HashSet::new();
+
+    for record in records.iter().filter(|r| in_range(query, r.timestamp)) {
+        let cost = match price_for(prices, &record.model) {
+            Some(price) => turn_cost(&record.usage, price),
+            None => {
+                unpriced.insert(record.model.clone());
+                0.0
+            }
+        };
+
+        totals.add(record, cost);
+        for (groups, key) in [
+            (&mut sessions, record.session_id.clone()),
+            (&mut models, record.model.clone()),
+            (&mut projects, record.project.clone()),
+            (&mut timeline, period_key(record.timestamp, query.group_by)),
+        ] {
+            groups.entry(key).or_default().add(record, cost);
+        }
+    }
+
+    let mut unpriced_models: Vec<String> = unpriced.into_iter().collect();
+    unpriced_models.sort();
+    UsageReport {
+        totals: totals.finish(),
+        by_session: by_cost(into_buckets(sessions)),
+        by_model: by_cost(into_buckets(models)),
+        by_project: by_cost(into_buckets(projects)),
+        timeline: into_buckets(timeline),
+        unpriced_models,
+        untracked_sessions: 0,
+    }
+}
+
+pub async fn query_token_usage(query: UsageQuery) -> Result<UsageReport, String> {
+    let prices = read_model_prices().await?;
+    tokio::task::spawn_blocking(move || {
+        let (records, untracked_sessions) = load_usage_records(&query)?;
+        Ok(UsageReport {
+            untracked_sessions,
+            ..aggregate_usage(&records, &prices, &query)
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to read token usage: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn price(input: f64, cached_input: f64, output: f64) -> ModelPrice {
+        ModelPrice {
+            input,
+            cached_input,
+            output,
+        }
+    }
+
+    fn usage(input: u64, cached: u64, output: u64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: cached,
+            output_tokens: output,
+            reasoning_output_tokens: 0,
+            total_tokens: input + output,
+        }
+    }
+
+    fn record(session_id: &str, model: &str, timestamp: i64) -> UsageRecord {
+        UsageRecord {
+            session_id: session_id.to_string(),
+            model: model.to_string(),
+            provider: "openai".to_string(),
+            project: "/work".to_string(),
+            timestamp,
+            usage: usage(1_000_000, 0, 0),
+        }
+    }
+
+    fn table(models: &[(&str, ModelPrice)]) -> PriceTable {
+        PriceTable {
+            models: models
+                .iter()
+                .map(|(name, price)| (name.to_string(), *price))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn price_for_prefers_the_longest_prefix() {
+        let prices = table(&[
+            ("gpt-5", price(1.0, 0.0, 0.0)),
+            ("gpt-5-mini", price(2.0, 0.0, 0.0)),
+        ]);
+        let input = |model| price_for(&prices, model).map(|p| p.input);
+        assert_eq!(input("gpt-5"), Some(1.0));
+        assert_eq!(input("gpt-5-codex"), Some(1.0));
+        assert_eq!(input("gpt-5-mini-2025-08-07"), Some(2.0));
+        assert_eq!(input("o3"), None);
+    }
+
+    #[test]
+    fn turn_cost_caps_cached_tokens_at_input() {
+        let price = price(2.0, 0.5, 8.0);
+        // 750k uncached + 250k cached input, 100k output
+        let cost = turn_cost(&usage(1_000_000, 250_000, 100_000), &price);
+        assert!((cost - (1.5 + 0.125 + 0.8)).abs() < 1e-9);
+
+        // More cached than input tokens is billed as all cached
+        let cost = turn_cost(&usage(1_000_000, 3_000_000, 0), &price);
+        assert!((cost - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn from_and_to_form_a_half_open_range() {
+        let records = [
+            record("a", "gpt-5", 99),
+            record("b", "gpt-5", 100),
+            record("c", "gpt-5", 200),
+        ];
+        let query = UsageQuery {
+            from: Some(100),
+            to: Some(200),
+            ..Default::default()
+        };
+        let report = aggregate_usage(&records, &PriceTable::default(), &query);
+        assert_eq!(report.totals.turns, 1);
+        assert_eq!(report.by_session[0].key, "b");
+    }
+
+    #[test]
+    fn week_keys_start_on_monday() {
+        let at = |day| {
+            Local
+                .with_ymd_and_hms(2025, 9, day, 12, 0, 0)
+                .unwrap()
+                .timestamp_millis()
+        };
+        // 2025-09-01 is a Monday
+        assert_eq!(period_key(at(1), UsageGrouping::Week), "2025-09-01");
+        assert_eq!(period_key(at(3), UsageGrouping::Week), "2025-09-01");
+        assert_eq!(period_key(at(7), UsageGrouping::Week), "2025-09-01");
+        assert_eq!(period_key(at(8), UsageGrouping::Week), "2025-09-08");
+        assert_eq!(period_key(at(8), UsageGrouping::Month), "2025-09");
+    }
+
+    #[test]
+    fn unpriced_models_are_listed_at_zero_cost() {
+        let prices = table(&[("gpt-5", price(1.0, 0.0, 0.0))]);
+        let records = [
+            record("a", "gpt-5", 0),
+            record("a", "llama3.2", 1),
+            record("b", "qwen", 2),
+            record("b", "qwen", 3),
+        ];
+        let report = aggregate_usage(&records, &prices, &UsageQuery::default());
+        assert_eq!(report.unpriced_models, ["llama3.2", "qwen"]);
+        assert!((report.totals.cost - 1.0).abs() < 1e-9);
+        assert_eq!((report.totals.turns, report.totals.sessions), (4, 2));
+    }
+
+    #[test]
+    fn rollout_usage_reads_token_count_events() {
+        let count = |total: u64, last: u64| {
+            format!(
+                r#"{{"timestamp":"2025-09-02T10:00:00.000Z","type":"event_msg","payload":{{"type":"token_count","info":{{"total_token_usage":{{"input_tokens":{total},"output_tokens":0,"total_tokens":{total}}},"last_token_usage":{{"input_tokens":{last},"output_tokens":0,"total_tokens":{last}}}}}}}}}"#
+            )
+        };
+        let content = [
+            r#"{"timestamp":"2025-09-02T09:59:00.000Z","type":"session_meta","payload":{"id":"7d3c9f20-58b1-4e2a-a6f4-91c0d2e3b745","timestamp":"2025-09-02T09:59:00.000Z","cwd":"/work","model_provider":"openai"}}"#.to_string(),
+            r#"{"timestamp":"2025-09-02T09:59:30.000Z","type":"turn_context","payload":{"cwd":"/work","model":"gpt-5-codex"}}"#.to_string(),
+            count(100, 100),
+            // A repeated count adds nothing
+            count(100, 100),
+            count(250, 150),
+        ]
+        .join("\n");
+
+        let usage = rollout_usage(&content, 0);
+        assert_eq!(
+            usage.session_id.as_deref(),
+            Some("7d3c9f20-58b1-4e2a-a6f4-91c0d2e3b745")
+        );
+        let inputs: Vec<u64> = usage.records.iter().map(|r| r.usage.input_tokens).collect();
+        assert_eq!(inputs, [100, 150]);
+        let first = &usage.records[0];
+        assert_eq!(
+            (
+                first.model.as_str(),
+                first.provider.as_str(),
+                first.project.as_str()
+            ),
+            ("gpt-5-codex", "openai", "/work")
+        );
+        assert_eq!(first.timestamp, 1756807200000);
+
+        let legacy = include_str!("../../tests/fixtures/rollouts/legacy.jsonl");
+        assert!(rollout_usage(legacy, 0).records.is_empty());
+    }
+}
@@ -0,0 +1,295 @@
+use super::search;
+use super::session::{self, ChatMessage, Conversation};
+use crate::protocol::HistoryEntry;
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+// Rollouts are appended line by line while a turn runs, so changes are
+// collected until the files go quiet, but never for longer than MAX_DELAY
+const DEBOUNCE: Duration = Duration::from_millis(300);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// New messages of a conversation that was already reported, starting with
+/// the previous last message if tool calls were added to it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionUpdated {
+    pub id: String,
+    pub title: String,
+    pub updated_at: i64,
+    pub file_path: Option<String>,
+    /// Index of the first message in `messages`; earlier ones are unchanged
+    pub first_message_index: usize,
+    pub messages: Vec<ChatMessage>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDeleted {
+    pub id: String,
+    pub file_path: Option<String>,
+}
+
+struct KnownSession {
+    id: String,
+    file_path: Option<String>,
+    /// None for files that existed before the watcher started
+    message_count: Option<usize>,
+    /// Tool calls and outputs keep landing in the last message's items while
+    /// a turn runs, so its content is tracked too
+    last_message_hash: u64,
+}
+
+fn message_hash(message: Option<&ChatMessage>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    if let Some(json) = message.and_then(|m| serde_json::to_string(m).ok()) {
+        json.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+struct SessionWatcher {
+    app: AppHandle,
+    sessions_dir: PathBuf,
+    history_path: PathBuf,
+    known: HashMap<PathBuf, KnownSession>,
+    history_offset: u64,
+}
+
+impl SessionWatcher {
+    fn new(app: AppHandle, sessions_dir: PathBuf, history_path: PathBuf) -> Self {
+        // Files on disk now are in the list the UI loads itself
+        let known = session::rollout_files(&sessions_dir)
+            .into_iter()
+            .filter_map(|file| {
+                let mut first_line = String::new();
+                BufReader::new(File::open(&file.path).ok()?)
+                    .read_line(&mut first_line)
+                    .ok()?;
+                let id = session::rollout_session_id(&first_line)?;
+                let known = KnownSession {
+                    id: format!("codex-event-{}", id),
+                    file_path: file
+                        .path
+                        .canonicalize()
+                        .ok()
+                        .and_then(|p| p.to_str().map(str::to_string)),
+                    message_count: None,
+                    last_message_hash: 0,
+                };
+                Some((file.path, known))
+            })
+            .collect();
+        let history_offset = fs::metadata(&history_path)
+            .map(|m| m.len())
+            .unwrap_or_default();
+
+        Self {
+            app,
+            sessions_dir,
+            history_path,
+            known,
+            history_offset,
+        }
+    }
+
+    fn is_rollout(&self, path: &Path) -> bool {
+        path.starts_with(&self.sessions_dir) && path.extension().is_some_and(|ext| ext == "jsonl")
+    }
+
+    fn handle_path(&mut self, path: &Path) {
+        if path == self.history_path {
+            self.history_changed();
+        } else if self.is_rollout(path) {
+            self.rollout_changed(path);
+        }
+    }
+
+    fn rollout_changed(&mut self, path: &Path) {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(_) if !path.exists() => {
+                if let Some(known) = self.known.remove(path) {
+                    let _ = self.app.emit(
+                        "session-deleted",
+                        SessionDeleted {
+                            id: known.id,
+                            file_path: known.file_path,
+                        },
+                    );
+                }
+                return;
+            }
+            Err(e) => {
+                log::warn!("Failed to read changed session {:?}: {}", path, e);
+                return;
+            }
+        };
+        let Some(conversation) = session::parse_session_file(&content, path) else {
+            return;
+        };
+
+        let message_count = conversation.messages.len();
+        let previous = self.known.insert(
+            path.to_path_buf(),
+            KnownSession {
+                id: conversation.id.clone(),
+                file_path: conversation.file_path.clone(),
+                message_count: Some(message_count),
+                last_message_hash: message_hash(conversation.messages.last()),
+            },
+        );
+        let Some(previous) = previous else {
+            let _ = self.app.emit("session-added", &conversation);
+            return;
+        };
+
+        // Rollouts only grow, so the new messages are at the end. The message
+        // that was last before is sent again if its items changed since.
+        let first_message_index = match previous.message_count {
+            Some(count) if count > 0 && count <= message_count => {
+                let was_last = &conversation.messages[count - 1];
+                if message_hash(Some(was_last)) != previous.last_message_hash {
+                    count - 1
+                } else {
+                    count
+                }
+            }
+            _ => 0,
+        };
+        if previous.message_count.is_some() && first_message_index == message_count {
+            return;
+        }
+        let Conversation {
+            id,
+            title,
+            messages,
+            updated_at,
+            file_path,
+            ..
+        } = conversation;
+        let _ = self.app.emit(
+            "session-updated",
+            SessionUpdated {
+                id,
+                title,
+                updated_at,
+                file_path,
+                first_message_index,
+                messages: messages.into_iter().skip(first_message_index).collect(),
+            },
+        );
+    }
+
+    fn history_changed(&mut self) {
+        let Ok(mut file) = File::open(&self.history_path) else {
+            self.history_offset = 0;
+            return;
+        };
+        let len = file.metadata().map(|m| m.len()).unwrap_or_default();
+        // Truncated or replaced; start over
+        if len < self.history_offset {
+            self.history_offset = 0;
+        }
+
+        let mut appended = String::new();
+        if file.seek(SeekFrom::Start(self.history_offset)).is_err()
+            || file.read_to_string(&mut appended).is_err()
+        {
+            return;
+        }
+        // Leave a partially written last line for the next change
+        let Some(complete) = appended.rfind('\n').map(|end| &appended[..=end]) else {
+            return;
+        };
+        self.history_offset += complete.len() as u64;
+
+        let entries: Vec<HistoryEntry> = complete
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        if !entries.is_empty() {
+            let _ = self.app.emit("history-appended", &entries);
+        }
+    }
+}
+
+/// Watch `~/.codex/sessions` and `~/.codex/history.jsonl` and report changes
+/// made by any codex process, including ones started outside the app.
+pub fn start_session_watcher(app: AppHandle) {
+    let sessions_dir = match session::sessions_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            log::warn!("Session watcher not started: {}", e);
+            return;
+        }
+    };
+    let Some(codex_dir) = sessions_dir.parent().map(Path::to_path_buf) else {
+        return;
+    };
+    if let Err(e) = fs::create_dir_all(&sessions_dir) {
+        log::warn!("Session watcher not started: {}", e);
+        return;
+    }
+
+    let (tx, rx) = mpsc::channel::<Vec<PathBuf>>();
+    let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            if !matches!(event.kind, EventKind::Access(_)) {
+                let _ = tx.send(event.paths);
+            }
+        }
+    })
+    .and_then(|mut watcher| {
+        watcher.watch(&sessions_dir, RecursiveMode::Recursive)?;
+        // history.jsonl may not exist yet, so watch the directory holding it
+        watcher.watch(&codex_dir, RecursiveMode::NonRecursive)?;
+        Ok(watcher)
+    });
+    let watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            log::warn!("Session watcher not started: {}", e);
+            return;
+        }
+    };
+
+    let history_path = codex_dir.join("history.jsonl");
+    std::thread::spawn(move || {
+        // Dropping the watcher stops it
+        let _watcher = watcher;
+        let mut state = SessionWatcher::new(app, sessions_dir, history_path);
+
+        while let Ok(first) = rx.recv() {
+            let mut changed: HashSet<PathBuf> = first.into_iter().collect();
+            let deadline = Instant::now() + MAX_DELAY;
+            while let Some(wait) = deadline
+                .checked_duration_since(Instant::now())
+                .map(|left| left.min(DEBOUNCE))
+            {
+                match rx.recv_timeout(wait) {
+                    Ok(paths) => changed.extend(paths),
+                    Err(_) => break,
+                }
+            }
+
+            for path in &changed {
+                state.handle_path(path);
+            }
+            let rollouts: Vec<PathBuf> = changed
+                .into_iter()
+                .filter(|path| state.is_rollout(path))
+                .collect();
+            search::update_index(&state.app, &rollouts);
+        }
+    });
+}
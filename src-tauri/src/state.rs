@@ -1,16 +1,116 @@
 use crate::codex_client::CodexClient;
+use crate::protocol::{Event, EventMsg};
+use crate::utils::time::current_timestamp_millis;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 pub struct CodexState {
     pub sessions: Arc<Mutex<HashMap<String, CodexClient>>>,
+    pub approvals: PendingApprovals,
 }
 
 impl CodexState {
     pub fn new() -> Self {
         Self {
             sessions: Arc::new(Mutex::new(HashMap::new())),
+            approvals: PendingApprovals::default(),
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalKind {
+    Exec,
+    Patch,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingApproval {
+    pub session_id: String,
+    /// Id of the request event, which codex expects back with the decision
+    pub approval_id: String,
+    pub call_id: String,
+    pub kind: ApprovalKind,
+    pub request: EventMsg,
+    pub timestamp: i64,
+}
+
+/// Approval requests codex is still waiting on, per session. Each request is
+/// answered at most once; later decisions for the same id are rejected.
+#[derive(Clone, Default)]
+pub struct PendingApprovals(Arc<std::sync::Mutex<HashMap<String, Vec<PendingApproval>>>>);
+
+impl PendingApprovals {
+    /// Track approval requests and forget them once the task they belong to ends.
+    pub fn observe(&self, session_id: &str, event: &Event) {
+        let (kind, call_id) = match &event.msg {
+            EventMsg::ExecApprovalRequest { call_id, .. } => (ApprovalKind::Exec, call_id),
+            EventMsg::ApplyPatchApprovalRequest { call_id, .. } => (ApprovalKind::Patch, call_id),
+            // codex drops outstanding requests when the task ends
+            EventMsg::TaskComplete { .. } | EventMsg::Error { .. } | EventMsg::ShutdownComplete => {
+                self.clear(session_id);
+                return;
+            }
+            _ => return,
+        };
+
+        self.insert(PendingApproval {
+            session_id: session_id.to_string(),
+            approval_id: event.id.clone(),
+            call_id: call_id.clone(),
+            kind,
+            request: event.msg.clone(),
+            timestamp: current_timestamp_millis(),
+        });
+    }
+
+    pub fn insert(&self, approval: PendingApproval) {
+        let mut map = self.0.lock().unwrap();
+        let pending = map.entry(approval.session_id.clone()).or_default();
+        pending.retain(|p| p.approval_id != approval.approval_id);
+        pending.push(approval);
+    }
+
+    pub fn list(&self, session_id: &str) -> Vec<PendingApproval> {
+        self.0
+            .lock()
+            .unwrap()
+            .get(session_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Remove the request a decision answers. `call_id`, when given, must match
+    /// too so a stale client cannot answer a newer request that reused the id.
+    pub fn take(
+        &self,
+        session_id: &str,
+        approval_id: &str,
+        kind: ApprovalKind,
+        call_id: Option<&str>,
+    ) -> Result<PendingApproval, String> {
+        let mut map = self.0.lock().unwrap();
+        let pending = map.get_mut(session_id);
+        let index = pending.as_ref().and_then(|pending| {
+            pending.iter().position(|p| {
+                p.approval_id == approval_id
+                    && p.kind == kind
+                    && call_id.is_none_or(|call_id| p.call_id == call_id)
+            })
+        });
+        match (pending, index) {
+            (Some(pending), Some(index)) => Ok(pending.remove(index)),
+            _ => Err(format!(
+                "No pending approval {} (unknown or already answered)",
+                approval_id
+            )),
+        }
+    }
+
+    pub fn clear(&self, session_id: &str) {
+        self.0.lock().unwrap().remove(session_id);
+    }
+}
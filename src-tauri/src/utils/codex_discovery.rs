@@ -13,23 +13,8 @@ fn get_platform_binary_name() -> &'static str {
     }
 }
 
-pub fn discover_codex_command() -> Option<PathBuf> {
-    let home = std::env::var("HOME").unwrap_or_default();
-    let binary_name = get_platform_binary_name();
-
-    // 0) Optional override via environment variable
-    if let Ok(explicit) = std::env::var("CODEX_PATH") {
-        let p = PathBuf::from(&explicit);
-        if p.exists() {
-            log::debug!("Using CODEX_PATH override at {}", p.display());
-            return Some(p);
-        } else {
-            log::warn!("CODEX_PATH provided but not found: {}", explicit);
-        }
-    }
-
-    // First priority: Check actual binary locations in node_modules
-    let binary_locations = [
+fn binary_locations(home: &str, binary_name: &str) -> [String; 4] {
+    [
         // Bun global installation
         format!(
             "{}/.bun/install/global/node_modules/@openai/codex/bin/{}",
@@ -49,9 +34,34 @@ pub fn discover_codex_command() -> Option<PathBuf> {
             "/opt/homebrew/lib/node_modules/@openai/codex/bin/{}",
             binary_name
         ),
-    ];
+    ]
+}
 
-    for path in &binary_locations {
+fn native_paths(home: &str) -> [String; 3] {
+    [
+        format!("{}/.cargo/bin/codex", home),
+        "/usr/local/bin/codex".to_string(),
+        "/opt/homebrew/bin/codex".to_string(),
+    ]
+}
+
+pub fn discover_codex_command() -> Option<PathBuf> {
+    let home = std::env::var("HOME").unwrap_or_default();
+    let binary_name = get_platform_binary_name();
+
+    // 0) Optional override via environment variable
+    if let Ok(explicit) = std::env::var("CODEX_PATH") {
+        let p = PathBuf::from(&explicit);
+        if p.exists() {
+            log::debug!("Using CODEX_PATH override at {}", p.display());
+            return Some(p);
+        } else {
+            log::warn!("CODEX_PATH provided but not found: {}", explicit);
+        }
+    }
+
+    // First priority: Check actual binary locations in node_modules
+    for path in &binary_locations(&home, binary_name) {
         let path_buf = PathBuf::from(path);
         if path_buf.exists() {
             log::debug!("Found codex binary at {}", path);
@@ -60,13 +70,7 @@ pub fn discover_codex_command() -> Option<PathBuf> {
     }
 
     // Second priority: Check if there are native rust/cargo installations
-    let native_paths = [
-        format!("{}/.cargo/bin/codex", home),
-        "/usr/local/bin/codex".to_string(),
-        "/opt/homebrew/bin/codex".to_string(),
-    ];
-
-    for path in &native_paths {
+    for path in &native_paths(&home) {
         let path_buf = PathBuf::from(path);
         if path_buf.exists() {
             // Check if it's a real binary (not a js wrapper)
@@ -119,3 +123,41 @@ pub fn discover_codex_command() -> Option<PathBuf> {
     log::warn!("No codex binary found in common locations or PATH");
     None
 }
+
+/// Shell script running the same search as `discover_codex_command` on a
+/// remote host. Prints the codex path and its `-V` output on separate lines.
+pub fn remote_discovery_script() -> String {
+    let quote_all = |paths: &[String]| {
+        paths
+            .iter()
+            .map(|p| format!("\"{}\"", p))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let binary_locations = quote_all(&binary_locations("$HOME", "$b"));
+    let native_paths = quote_all(&native_paths("$HOME"));
+
+    format!(
+        r#"case "$(uname -s)-$(uname -m)" in
+  Darwin-arm64) b=codex-aarch64-apple-darwin ;;
+  Darwin-x86_64) b=codex-x86_64-apple-darwin ;;
+  Linux-x86_64) b=codex-x86_64-unknown-linux-musl ;;
+  Linux-aarch64|Linux-arm64) b=codex-aarch64-unknown-linux-musl ;;
+  *) b=codex ;;
+esac
+is_wrapper() {{ grep -q -e codex.js -e '^#!/usr/bin/env node' "$1" 2>/dev/null; }}
+found() {{ echo "$1"; "$1" -V 2>&1 | head -n 1; exit 0; }}
+[ -n "$CODEX_PATH" ] && [ -e "$CODEX_PATH" ] && found "$CODEX_PATH"
+for p in {binary_locations}; do [ -e "$p" ] && found "$p"; done
+for p in {native_paths}; do [ -e "$p" ] && ! is_wrapper "$p" && found "$p"; done
+wrapper=
+IFS=:
+for d in $PATH; do
+  [ -n "$d" ] && [ -e "$d/codex" ] || continue
+  if is_wrapper "$d/codex" || grep -q import "$d/codex" 2>/dev/null; then wrapper="$d/codex"; continue; fi
+  found "$d/codex"
+done
+[ -n "$wrapper" ] && found "$wrapper"
+exit 1"#
+    )
+}
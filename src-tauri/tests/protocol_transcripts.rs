@@ -0,0 +1,134 @@
+//! Replays recorded `codex proto` transcripts through the event parser so that
+//! protocol changes in new codex releases show up here instead of as silently
+//! dropped events in the UI.
+//!
+//! Fixtures live in `tests/fixtures/proto/*.jsonl`, one event per line exactly
+//! as codex printed it on stdout.
+
+#[path = "../src/protocol.rs"]
+#[allow(dead_code)]
+mod protocol;
+
+use protocol::{parse_event, EventMsg};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+fn fixtures() -> Vec<(String, String)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/proto");
+    let mut files: Vec<_> = fs::read_dir(&dir)
+        .expect("fixture directory")
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    files.sort();
+    assert!(!files.is_empty(), "no transcripts in {}", dir.display());
+
+    files
+        .into_iter()
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            let content = fs::read_to_string(&path).expect("read fixture");
+            (name, content)
+        })
+        .collect()
+}
+
+fn event_type(msg: &EventMsg) -> String {
+    let value = serde_json::to_value(msg).expect("serialize event");
+    value["type"].as_str().unwrap_or_default().to_string()
+}
+
+#[test]
+fn every_recorded_line_parses() {
+    for (name, content) in fixtures() {
+        for (n, line) in content.lines().enumerate() {
+            let raw: Value = serde_json::from_str(line).unwrap();
+            let event = parse_event(line)
+                .unwrap_or_else(|e| panic!("{}:{}: failed to parse: {}", name, n + 1, e));
+
+            assert_eq!(event.id, raw["id"].as_str().unwrap(), "{}:{}", name, n + 1);
+            assert_eq!(
+                event_type(&event.msg),
+                raw["msg"]["type"].as_str().unwrap(),
+                "{}:{}: event type changed while parsing",
+                name,
+                n + 1
+            );
+        }
+    }
+}
+
+#[test]
+fn recorded_events_are_typed() {
+    // Everything current codex emits should have a typed variant
+    for (name, content) in fixtures() {
+        for (n, line) in content.lines().enumerate() {
+            let event = parse_event(line).unwrap();
+            if let EventMsg::Unknown(msg) = &event.msg {
+                panic!("{}:{}: no typed variant for {}", name, n + 1, msg["type"]);
+            }
+        }
+    }
+}
+
+#[test]
+fn typed_payloads_round_trip() {
+    for (name, content) in fixtures() {
+        for (n, line) in content.lines().enumerate() {
+            let event = parse_event(line).unwrap();
+            let reparsed = parse_event(&serde_json::to_string(&event).unwrap()).unwrap();
+            assert_eq!(
+                serde_json::to_value(&event.msg).unwrap(),
+                serde_json::to_value(&reparsed.msg).unwrap(),
+                "{}:{}",
+                name,
+                n + 1
+            );
+        }
+    }
+}
+
+#[test]
+fn token_count_reports_session_totals() {
+    let line = r#"{"id":"1","msg":{"type":"token_count","info":{"total_token_usage":{"input_tokens":10,"output_tokens":5,"total_tokens":15},"last_token_usage":{"input_tokens":10,"output_tokens":5,"total_tokens":15}}}}"#;
+    let EventMsg::TokenCount { info: Some(info) } = parse_event(line).unwrap().msg else {
+        panic!("expected token_count with usage info");
+    };
+    assert_eq!(info.total_token_usage.total_tokens, 15);
+    assert_eq!(info.total_token_usage.cached_input_tokens, 0);
+    assert_eq!(info.model_context_window, None);
+}
+
+#[test]
+fn unknown_event_keeps_raw_payload() {
+    let line = r#"{"id":"7","msg":{"type":"some_future_event","call_id":"call_z9","detail":{"nested":[1,2,3]},"flag":true}}"#;
+    let event = parse_event(line).unwrap();
+
+    let EventMsg::Unknown(payload) = &event.msg else {
+        panic!("expected Unknown, got {:?}", event.msg);
+    };
+    let raw: Value = serde_json::from_str(line).unwrap();
+    assert_eq!(payload, &raw["msg"]);
+
+    // Forwarded to the frontend unchanged
+    let reserialized = serde_json::to_value(&event).unwrap();
+    assert_eq!(reserialized, raw);
+}
+
+#[test]
+fn known_event_with_mismatched_payload_is_not_dropped() {
+    // A known type whose fields changed shape must still reach the frontend
+    let line = r#"{"id":"1","msg":{"type":"agent_message","message":{"parts":["hi"]}}}"#;
+    let event = parse_event(line).unwrap();
+
+    assert!(matches!(event.msg, EventMsg::Unknown(_)));
+    assert_eq!(event_type(&event.msg), "agent_message");
+}
+
+#[test]
+fn non_event_lines_are_rejected() {
+    assert!(parse_event("Reading prompt from stdin...").is_err());
+    assert!(parse_event(r#"{"id":"1"}"#).is_err());
+    assert!(parse_event(r#"{"id":"1","msg":{"text":"no type"}}"#).is_err());
+}
@@ -6,8 +6,9 @@ import { useLayoutStore } from "@/stores/layoutStore";
 import { useInstanceStore } from "@/stores/InstanceStore";
 import { sessionManager } from "@/services/sessionManager";
 import { sessionLoader } from "@/services/sessionLoader";
-import type { Conversation } from "@/types/chat";
+import type { Conversation, SessionDeletedEvent, SessionUpdatedEvent } from "@/types/chat";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { DebugInfo } from "./common/DebugInfo";
 
 export const ChatView: React.FC = () => {
@@ -59,16 +60,19 @@ export const ChatView: React.FC = () => {
 
   const loadHistory = async () => {
     try {
-      const history = await sessionLoader.loadSessionsFromDisk();
-      setHistoryConversations(history);
-
-      const statuses: Record<string, boolean> = {};
-      for (const conv of history) {
-        statuses[conv.id] = await sessionLoader.isConversationFavorited(
-          conv.id,
-        );
-      }
-      setFavoriteStatuses(statuses);
+      let loaded: Conversation[] = [];
+      await sessionLoader.loadSessionsInPages(async (page) => {
+        loaded = [...loaded, ...page];
+        setHistoryConversations(loaded);
+
+        const statuses: Record<string, boolean> = {};
+        for (const conv of page) {
+          statuses[conv.id] = await sessionLoader.isConversationFavorited(
+            conv.id,
+          );
+        }
+        setFavoriteStatuses((prev) => ({ ...prev, ...statuses }));
+      });
     } catch (error) {
       console.error("Failed to load history conversations:", error);
     }
@@ -78,6 +82,51 @@ export const ChatView: React.FC = () => {
     loadHistory();
   }, []);
 
+  // Keep the history list in sync with session files written by any codex,
+  // including the terminal CLI
+  useEffect(() => {
+    const addedUnlisten = listen<Conversation>("session-added", (event) => {
+      const added = event.payload;
+      setHistoryConversations((prev) => [added, ...prev.filter((c) => c.id !== added.id)]);
+    });
+
+    const updatedUnlisten = listen<SessionUpdatedEvent>("session-updated", (event) => {
+      const update = event.payload;
+      setHistoryConversations((prev) => {
+        const existing = prev.find((c) => c.id === update.id);
+        const updated: Conversation = {
+          ...(existing ?? {
+            id: update.id,
+            mode: "agent",
+            createdAt: update.updatedAt,
+            isFavorite: false,
+          }),
+          title: update.title,
+          updatedAt: update.updatedAt,
+          filePath: update.filePath ?? undefined,
+          messages: [
+            ...(existing?.messages.slice(0, update.firstMessageIndex) ?? []),
+            ...update.messages,
+          ],
+        } as Conversation;
+        return existing
+          ? prev.map((c) => (c.id === update.id ? updated : c))
+          : [updated, ...prev];
+      });
+    });
+
+    const deletedUnlisten = listen<SessionDeletedEvent>("session-deleted", (event) => {
+      const { id } = event.payload;
+      setHistoryConversations((prev) => prev.filter((c) => c.id !== id));
+    });
+
+    return () => {
+      addedUnlisten.then((fn) => fn());
+      updatedUnlisten.then((fn) => fn());
+      deletedUnlisten.then((fn) => fn());
+    };
+  }, []);
+
   const activeConversations = useMemo(() => allConversations.filter(c => !activeId || c.instanceId === activeId), [allConversations, activeId]);
   const filteredHistory = useMemo(() => historyConversations.filter(c => !activeId || c.instanceId === activeId), [historyConversations, activeId]);
 
@@ -103,6 +152,12 @@ export const ChatView: React.FC = () => {
     selectHistoryConversation(conversationCopy);
   };
 
+  // Show the fork in the history list and open it, ready to be resumed
+  const handleForkConversation = (conversation: Conversation) => {
+    setHistoryConversations((prev) => [conversation, ...prev]);
+    handleConversationSelect(conversation);
+  };
+
   const handleSelectSession = (sessionId: string) => {
     // Set the current session ID for communication
     setCurrentConversation(sessionId);
@@ -224,6 +279,7 @@ export const ChatView: React.FC = () => {
           onCloseSession={deleteConversation}
           isSessionListVisible={false}
           selectedConversation={selectedConversation}
+          onForkConversation={handleForkConversation}
         />
       </div>
     </div>
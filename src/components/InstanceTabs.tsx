@@ -14,7 +14,7 @@ export function InstanceTabs() {
     void load()
   }, [load])
 
-  const addInstance = (data: { name: string; type: "local" | "ssh"; host?: string; port?: number; username?: string; keyPath?: string }) => {
+  const addInstance = (data: { name: string; type: "local" | "ssh"; host?: string; port?: number; username?: string; keyPath?: string; auth?: "key" | "agent" | "password" }) => {
     const id = Math.random().toString(36).slice(2)
     const instance: Instance = { id, status: "ready", ...data }
     void add(instance)
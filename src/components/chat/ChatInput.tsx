@@ -18,11 +18,12 @@ import { useChatInputStore } from '../../stores/chatInputStore';
 import { useSettingsStore } from '../../stores/SettingsStore';
 import { useModelStore } from '../../stores/ModelStore';
 import { ConfigService } from '../../services/configService';
+import { Attachment } from '@/types/codex';
 
 interface ChatInputProps {
   inputValue: string;
   onInputChange: (value: string) => void;
-  onSendMessage: (message: string) => void;
+  onSendMessage: (message: string, attachments: Attachment[]) => void;
   onStopStreaming?: () => void;
   disabled?: boolean;
   isLoading?: boolean;
@@ -48,6 +49,7 @@ export const ChatInput: React.FC<ChatInputProps> = ({
   const { currentModel, currentProvider, setCurrentModel } = useModelStore();
   const [modelsByProvider, setModelsByProvider] = useState<Record<string, Array<{model: string, source: 'settings' | 'config'}>>>({});
   const [isModelPopoverOpen, setIsModelPopoverOpen] = useState(false);
+  const [pastedImages, setPastedImages] = useState<string[]>([]);
 
   // Load available models grouped by provider
   useEffect(() => {
@@ -141,9 +143,37 @@ export const ChatInput: React.FC<ChatInputProps> = ({
       messageContent = `${smartPrompt}\n\n${inputValue}`;
     }
 
-    onSendMessage(messageContent);
+    // Files are sent with their contents; folders stay as path mentions
+    const attachments: Attachment[] = [
+      ...fileReferences
+        .filter(ref => !ref.isDirectory)
+        .map(ref => ({ type: 'file' as const, path: ref.path })),
+      ...pastedImages.map(url => ({ type: 'imageData' as const, url })),
+    ];
+
+    onSendMessage(messageContent, attachments);
     onInputChange('');
     clearFileReferences();
+    setPastedImages([]);
+  };
+
+  const handlePaste = (e: React.ClipboardEvent) => {
+    const images = Array.from(e.clipboardData.items)
+      .filter(item => item.type.startsWith('image/'))
+      .map(item => item.getAsFile())
+      .filter((file): file is File => file !== null);
+    if (images.length === 0) return;
+
+    e.preventDefault();
+    images.forEach(file => {
+      const reader = new FileReader();
+      reader.onload = () => {
+        if (typeof reader.result === 'string') {
+          setPastedImages(prev => [...prev, reader.result as string]);
+        }
+      };
+      reader.readAsDataURL(file);
+    });
   };
 
   const handleStopStreaming = () => {
@@ -200,11 +230,26 @@ export const ChatInput: React.FC<ChatInputProps> = ({
         </div>
       )}
       
+      {pastedImages.length > 0 && (
+        <div className="mb-3 flex flex-wrap gap-2">
+          {pastedImages.map((url, index) => (
+            <div key={index} className="relative">
+              <img src={url} alt="Pasted image" className="h-16 w-16 rounded border object-cover" />
+              <X
+                className="absolute -top-1 -right-1 w-4 h-4 bg-white rounded-full border cursor-pointer"
+                onClick={() => setPastedImages(prev => prev.filter((_, i) => i !== index))}
+              />
+            </div>
+          ))}
+        </div>
+      )}
+
       <div className="flex gap-2">
         <Textarea
           value={inputValue}
           onChange={(e) => onInputChange(e.target.value)}
           onKeyDown={handleKeyPress}
+          onPaste={handlePaste}
           placeholder={placeholderOverride || "Type your message..."}
           className="flex-1 min-h-[40px] max-h-[120px]"
           disabled={false}
@@ -1,6 +1,6 @@
 import React, { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { ApprovalRequest, CodexConfig } from "@/types/codex";
+import { ApprovalRequest, Attachment, CodexConfig, ReviewDecision } from "@/types/codex";
 import type { Conversation } from "@/types/chat";
 import { useConversationStore } from "../../stores/ConversationStore";
 import { useInstanceStore } from "../../stores/InstanceStore";
@@ -22,6 +22,7 @@ interface ChatInterfaceProps {
   onCloseSession?: (sessionId: string) => void;
   isSessionListVisible?: boolean;
   selectedConversation?: Conversation | null;
+  onForkConversation?: (conversation: Conversation) => void;
 }
 
 export const ChatInterface: React.FC<ChatInterfaceProps> = ({
@@ -33,6 +34,7 @@ export const ChatInterface: React.FC<ChatInterfaceProps> = ({
   onCloseSession,
   isSessionListVisible = false,
   selectedConversation = null,
+  onForkConversation,
 }) => {
   const { inputValue, setInputValue } = useChatInputStore();
   const { currentModel, currentProvider } = useModelStore();
@@ -56,8 +58,10 @@ export const ChatInterface: React.FC<ChatInterfaceProps> = ({
   const { activeId } = useInstanceStore();
 
   // Simplified: Use session_id to find conversation data
-  // Priority: selectedConversation (from disk/history) > conversations (from store)
-  const currentConversation = selectedConversation ||
+  // Priority: resumed history (in store) > selectedConversation (from disk/history) > conversations (from store)
+  const currentConversation =
+    (selectedConversation && conversations.find((conv) => conv.id === selectedConversation.id)) ||
+    selectedConversation ||
     conversations.find((conv) => conv.id === currentConversationId) ||
     conversations.find((conv) => conv.id === sessionId);
 
@@ -82,7 +86,8 @@ export const ChatInterface: React.FC<ChatInterfaceProps> = ({
               : "system") as "user" | "agent" | "system",
           content: msg.content,
           timestamp: new Date(typeof msg.timestamp === 'number' ? msg.timestamp : Date.now()),
-          model: msg.role === "assistant" ? currentModel : undefined
+          model: msg.role === "assistant" ? currentModel : undefined,
+          items: msg.items
         };
       })
     : [];
@@ -170,23 +175,54 @@ export const ChatInterface: React.FC<ChatInterfaceProps> = ({
     }
   }, [sessionId, selectedConversation, pendingNewConversation, sessionStarting]);
 
-  const handleSendMessage = async (messageContent: string) => {
+  // Apply model, approval and sandbox changes to the running session without
+  // losing the conversation
+  useEffect(() => {
+    if (!sessionId || !sessionManager.isSessionRunning(sessionId)) return;
+
+    const updatedConfig = {
+      ...config,
+      model: currentModel,
+      provider: currentProvider,
+      useOss: currentProvider.toLowerCase() !== 'openai'
+    };
+    sessionManager.updateSessionSettings(sessionId, updatedConfig).catch((error) => {
+      console.error("Failed to update session settings:", error);
+      addMessage(sessionId, {
+        id: `${sessionId}-settings-error-${Date.now()}-${Math.random().toString(36).substring(2, 11)}`,
+        role: "system" as const,
+        content: `Failed to update session settings: ${error}`,
+        timestamp: Date.now(),
+      });
+    });
+  }, [sessionId, config, currentModel, currentProvider]);
+
+  const handleSendMessage = async (messageContent: string, attachments: Attachment[] = []) => {
     console.log("isConnected", isConnected)
     
     if (!messageContent.trim() || isLoading) {
       return;
     }
 
-    // Prevent sending messages when viewing historical conversations
-    if (selectedConversation) {
+    // Historical conversations can only be continued by resuming their rollout
+    if (selectedConversation && !selectedConversation.filePath) {
       return;
     }
+    const resumePath = selectedConversation?.filePath;
 
-    let actualSessionId = sessionId;
+    let actualSessionId = resumePath ? selectedConversation.id : sessionId;
 
     // Handle pending new conversation, temporary sessionId, or empty sessionId
     let isPendingSession = false;
-    if (pendingNewConversation || !sessionId.trim()) {
+    if (resumePath) {
+      // Copy the history into the store so the resumed session keeps showing it
+      if (!conversations.find((conv) => conv.id === actualSessionId)) {
+        createConversation(selectedConversation.title, "agent", actualSessionId, activeId || undefined);
+        selectedConversation.messages.forEach((msg) => addMessage(actualSessionId, msg));
+      }
+      setCurrentConversation(actualSessionId);
+      setActiveSessionId(actualSessionId);
+    } else if (pendingNewConversation || !sessionId.trim()) {
       // Check if there's already a conversation waiting to be used
       const existingNewConversation = conversations.find(conv => 
         conv.id.startsWith('codex-event-') && 
@@ -237,7 +273,7 @@ export const ChatInterface: React.FC<ChatInterfaceProps> = ({
           provider: currentProvider,
           useOss: currentProvider.toLowerCase() !== 'openai'
         };
-        await sessionManager.ensureSessionRunning(actualSessionId, updatedConfig);
+        await sessionManager.ensureSessionRunning(actualSessionId, updatedConfig, resumePath);
         setIsConnected(true);
         
         if (isPendingSession) {
@@ -280,10 +316,18 @@ export const ChatInterface: React.FC<ChatInterfaceProps> = ({
         ? actualSessionId.replace('codex-event-', '') 
         : actualSessionId;
 
-      await invoke("send_message", {
-        sessionId: rawSessionId,
-        message: messageContent,
-      });
+      if (attachments.length > 0) {
+        await invoke("send_message_with_attachments", {
+          sessionId: rawSessionId,
+          message: messageContent,
+          attachments,
+        });
+      } else {
+        await invoke("send_message", {
+          sessionId: rawSessionId,
+          message: messageContent,
+        });
+      }
     } catch (error) {
       console.error("Failed to send message:", error);
       const errorMessage = {
@@ -297,7 +341,7 @@ export const ChatInterface: React.FC<ChatInterfaceProps> = ({
     }
   };
 
-  const handleApproval = async (approved: boolean) => {
+  const handleApproval = async (decision: ReviewDecision) => {
     if (!pendingApproval) return;
 
     try {
@@ -306,10 +350,11 @@ export const ChatInterface: React.FC<ChatInterfaceProps> = ({
         ? sessionId.replace('codex-event-', '') 
         : sessionId;
 
-      await invoke("approve_execution", {
+      await invoke(pendingApproval.type === 'exec' ? "approve_execution" : "approve_patch", {
         sessionId: rawSessionId,
         approvalId: pendingApproval.id,
-        approved,
+        callId: pendingApproval.callId,
+        decision,
       });
       setPendingApproval(null);
     } catch (error) {
@@ -317,6 +362,23 @@ export const ChatInterface: React.FC<ChatInterfaceProps> = ({
     }
   };
 
+  // Forking copies the rollout file, so it needs a conversation that was loaded from disk
+  const forkPath = currentConversation?.filePath;
+  const handleForkMessage = async (messageIndex: number) => {
+    if (!forkPath) return;
+
+    try {
+      const forked = await invoke<Conversation>("fork_session", {
+        filePath: forkPath,
+        messageIndex,
+        config: null,
+      });
+      onForkConversation?.(forked);
+    } catch (error) {
+      console.error("Failed to fork conversation:", error);
+    }
+  };
+
   const handleStopStreaming = async () => {
     try {
       // Extract raw session ID for backend communication
@@ -362,6 +424,7 @@ export const ChatInterface: React.FC<ChatInterfaceProps> = ({
           messages={messages} 
           isLoading={isLoading} 
           isPendingNewConversation={pendingNewConversation || !sessionId.trim()}
+          onForkMessage={forkPath && onForkConversation ? handleForkMessage : undefined}
         />
 
         <ApprovalDialog
@@ -374,7 +437,7 @@ export const ChatInterface: React.FC<ChatInterfaceProps> = ({
           onInputChange={setInputValue}
           onSendMessage={handleSendMessage}
           onStopStreaming={handleStopStreaming}
-          disabled={!!selectedConversation}
+          disabled={!!selectedConversation && !selectedConversation.filePath}
           isLoading={isLoading}
         />
       </div>
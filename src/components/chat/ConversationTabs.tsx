@@ -4,8 +4,9 @@ import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Circle, X, Search, RefreshCw } from "lucide-react";
 import { useLayoutStore } from "@/stores/layoutStore";
+import { sessionLoader } from "@/services/sessionLoader";
 import type { Conversation } from "@/types/chat";
-import React, { useMemo } from "react";
+import React, { useEffect, useMemo, useState } from "react";
 
 interface SearchQueries {
   all: string;
@@ -44,6 +45,30 @@ export function ConversationTabs({
   onRefreshConversations,
 }: ChatTabsProps) {
   const { conversationListTab, setConversationListTab } = useLayoutStore();
+  // Sessions whose content matches the search, from the full-text index
+  const [indexMatches, setIndexMatches] = useState<Set<string>>(new Set());
+
+  const historySearchQuery =
+    conversationListTab === "sessions" ? "" : searchQueries[conversationListTab as keyof SearchQueries] || "";
+
+  useEffect(() => {
+    if (!historySearchQuery.trim()) {
+      setIndexMatches(new Set());
+      return;
+    }
+
+    let cancelled = false;
+    const timer = setTimeout(async () => {
+      const hits = await sessionLoader.searchSessions(historySearchQuery, 200);
+      if (!cancelled) {
+        setIndexMatches(new Set(hits.map((hit) => hit.session_id)));
+      }
+    }, 300);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [historySearchQuery]);
 
   const getFilteredConversations = (tab: string, searchQuery: string) => {
     let conversations: Conversation[] = [];
@@ -65,6 +90,7 @@ export function ConversationTabs({
       const query = searchQuery.toLowerCase();
       conversations = conversations.filter(
         (conversation) =>
+          indexMatches.has(conversation.id) ||
           conversation.title.toLowerCase().includes(query) ||
           conversation.messages.some((msg) =>
             msg.content.toLowerCase().includes(query),
@@ -78,7 +104,7 @@ export function ConversationTabs({
   const filteredConversations = useMemo(() => {
     const currentSearchQuery = searchQueries[conversationListTab as keyof SearchQueries] || '';
     return getFilteredConversations(conversationListTab, currentSearchQuery);
-  }, [conversationListTab, searchQueries, historyConversations, activeConversations, favoriteStatuses]);
+  }, [conversationListTab, searchQueries, historyConversations, activeConversations, favoriteStatuses, indexMatches]);
 
   const handleSearchChange = (value: string) => {
     onSearchChange({
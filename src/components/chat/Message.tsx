@@ -1,8 +1,10 @@
 import { memo } from 'react';
-import { Copy, Check, ChevronDown, ChevronRight } from 'lucide-react';
+import { Copy, Check, ChevronDown, ChevronRight, GitBranch } from 'lucide-react';
 import { MessageNoteActions } from './MessageNoteActions';
 import { MarkdownRenderer } from './MarkdownRenderer';
 import { StreamingMessage } from '../StreamingMessage';
+import { TurnItemList } from './TurnItemList';
+import type { TurnItem } from '@/types/chat';
 import { useState } from 'react';
 import {
   Collapsible,
@@ -17,6 +19,7 @@ interface NormalizedMessage {
   timestamp: number;
   isStreaming: boolean;
   model?: string;
+  items?: TurnItem[];
 }
 
 interface MessageProps {
@@ -24,6 +27,7 @@ interface MessageProps {
   index: number;
   isLastMessage: boolean;
   selectedText: string;
+  onFork?: (index: number) => void;
 }
 
 
@@ -51,7 +55,8 @@ export const Message = memo<MessageProps>(({
   message: normalized, 
   index, 
   isLastMessage, 
-  selectedText 
+  selectedText,
+  onFork
 }) => {
   const [copied, setCopied] = useState(false);
   const [isCollapsed, setIsCollapsed] = useState(true);
@@ -148,6 +153,15 @@ export const Message = memo<MessageProps>(({
                   <Copy className="w-4 h-4 text-gray-600" />
                 )}
               </button>
+              {onFork && (
+                <button
+                  onClick={() => onFork(index)}
+                  className="p-1 hover:bg-gray-200 rounded transition-colors"
+                  title="Fork conversation from here"
+                >
+                  <GitBranch className="w-4 h-4 text-gray-600" />
+                </button>
+              )}
               <MessageNoteActions
                 messageId={normalized.id}
                 messageContent={normalized.content}
@@ -159,6 +173,7 @@ export const Message = memo<MessageProps>(({
           </div>
         </div>
       }
+      {normalized.items && <TurnItemList items={normalized.items} />}
     </div>
   );
 });
\ No newline at end of file
@@ -14,9 +14,10 @@ interface MessageListProps {
   className?: string;
   isLoading?: boolean;
   isPendingNewConversation?: boolean;
+  onForkMessage?: (index: number) => void;
 }
 
-export function MessageList({ messages, className = "", isLoading = false, isPendingNewConversation = false }: MessageListProps) {
+export function MessageList({ messages, className = "", isLoading = false, isPendingNewConversation = false, onForkMessage }: MessageListProps) {
   const messagesEndRef = useRef<HTMLDivElement>(null);
   const messagesContainerRef = useRef<HTMLDivElement>(null);
   const [showScrollButtons, setShowScrollButtons] = useState(false);
@@ -70,7 +71,8 @@ export function MessageList({ messages, className = "", isLoading = false, isPen
         timestamp: msg.timestamp instanceof Date ? msg.timestamp.getTime() : new Date().getTime(),
         isStreaming: msg.isStreaming || false,
         model: 'model' in msg ? (msg.model as string) : undefined,
-        workingDirectory: 'workingDirectory' in msg ? (msg.workingDirectory as string) : undefined
+        workingDirectory: 'workingDirectory' in msg ? (msg.workingDirectory as string) : undefined,
+        items: msg.items
       };
     }
     // It's a chat message (has 'role' property)
@@ -81,7 +83,8 @@ export function MessageList({ messages, className = "", isLoading = false, isPen
       timestamp: typeof msg.timestamp === 'number' ? msg.timestamp : new Date().getTime(),
       isStreaming: false,
       model: msg.model as string | undefined,
-      workingDirectory: msg.workingDirectory as string | undefined
+      workingDirectory: msg.workingDirectory as string | undefined,
+      items: msg.items
     };
   }, []);
 
@@ -133,6 +136,7 @@ export function MessageList({ messages, className = "", isLoading = false, isPen
               index={index}
               isLastMessage={index === messages.length - 1}
               selectedText={selectedText}
+              onFork={onForkMessage}
             />
           ))}
           
@@ -0,0 +1,163 @@
+import { useState } from 'react';
+import { Brain, ChevronDown, ChevronRight, FileDiff, Plug, Terminal, Wrench } from 'lucide-react';
+import type { TurnItem } from '@/types/chat';
+import {
+  Collapsible,
+  CollapsibleContent,
+  CollapsibleTrigger,
+} from "@/components/ui/collapsible";
+import { MarkdownRenderer } from './MarkdownRenderer';
+
+interface TurnItemListProps {
+  items: TurnItem[];
+}
+
+const statusClass = (ok: boolean | null | undefined) => {
+  if (ok === true) return 'text-green-600';
+  if (ok === false) return 'text-red-600';
+  return 'text-gray-500';
+};
+
+const OutputBlock = ({ text }: { text: string }) => (
+  <pre className="bg-gray-50 border rounded-md p-2 overflow-x-auto text-xs whitespace-pre-wrap break-words max-h-80 overflow-y-auto select-text">
+    {text}
+  </pre>
+);
+
+const PatchBlock = ({ patch }: { patch: string }) => (
+  <pre className="bg-gray-50 border rounded-md p-2 overflow-x-auto text-xs max-h-80 overflow-y-auto select-text">
+    {patch.split('\n').map((line, i) => (
+      <div
+        key={i}
+        className={
+          line.startsWith('+') ? 'text-green-700' : line.startsWith('-') ? 'text-red-700' : ''
+        }
+      >
+        {line || ' '}
+      </div>
+    ))}
+  </pre>
+);
+
+function ItemHeader({ item }: { item: TurnItem }) {
+  switch (item.type) {
+    case 'exec':
+      return (
+        <>
+          <Terminal className="w-3.5 h-3.5 shrink-0" />
+          <span className="font-mono truncate">{item.command.join(' ')}</span>
+          {item.exitCode != null && (
+            <span className={`ml-auto shrink-0 ${statusClass(item.exitCode === 0)}`}>
+              exit {item.exitCode}
+            </span>
+          )}
+        </>
+      );
+    case 'patch':
+      return (
+        <>
+          <FileDiff className="w-3.5 h-3.5 shrink-0" />
+          <span className="truncate">
+            {item.files.length > 0 ? item.files.join(', ') : 'Patch'}
+          </span>
+          {item.success != null && (
+            <span className={`ml-auto shrink-0 ${statusClass(item.success)}`}>
+              {item.success ? 'applied' : 'failed'}
+            </span>
+          )}
+        </>
+      );
+    case 'reasoning':
+      return (
+        <>
+          <Brain className="w-3.5 h-3.5 shrink-0" />
+          <span className="truncate">{item.summary.split('\n')[0].replace(/\*\*/g, '')}</span>
+        </>
+      );
+    case 'mcp_tool_call':
+      return (
+        <>
+          <Plug className="w-3.5 h-3.5 shrink-0" />
+          <span className="font-mono truncate">{item.server}.{item.tool}</span>
+          {item.success != null && (
+            <span className={`ml-auto shrink-0 ${statusClass(item.success)}`}>
+              {item.success ? 'ok' : 'error'}
+            </span>
+          )}
+        </>
+      );
+    case 'tool_call':
+      return (
+        <>
+          <Wrench className="w-3.5 h-3.5 shrink-0" />
+          <span className="font-mono truncate">{item.name}</span>
+        </>
+      );
+  }
+}
+
+function ItemBody({ item }: { item: TurnItem }) {
+  switch (item.type) {
+    case 'exec':
+      return item.output ? <OutputBlock text={item.output} /> : null;
+    case 'patch':
+      return (
+        <>
+          <PatchBlock patch={item.patch} />
+          {item.output && <OutputBlock text={item.output} />}
+        </>
+      );
+    case 'reasoning':
+      return <MarkdownRenderer content={item.summary} />;
+    case 'mcp_tool_call':
+      return (
+        <>
+          <OutputBlock text={JSON.stringify(item.arguments, null, 2)} />
+          {item.output && <OutputBlock text={item.output} />}
+        </>
+      );
+    case 'tool_call':
+      return (
+        <>
+          <OutputBlock text={item.arguments} />
+          {item.output && <OutputBlock text={item.output} />}
+        </>
+      );
+  }
+}
+
+function TurnItemRow({ item }: { item: TurnItem }) {
+  const [open, setOpen] = useState(false);
+
+  return (
+    <Collapsible open={open} onOpenChange={setOpen}>
+      <CollapsibleTrigger asChild>
+        <div className="flex items-center gap-2 w-full min-w-0 text-left text-xs text-gray-600 hover:bg-gray-100 px-2 py-1 rounded cursor-pointer">
+          {open ? (
+            <ChevronDown className="w-3.5 h-3.5 shrink-0" />
+          ) : (
+            <ChevronRight className="w-3.5 h-3.5 shrink-0" />
+          )}
+          <ItemHeader item={item} />
+        </div>
+      </CollapsibleTrigger>
+      <CollapsibleContent className="mt-1 mb-2 ml-6 space-y-1 min-w-0">
+        <ItemBody item={item} />
+      </CollapsibleContent>
+    </Collapsible>
+  );
+}
+
+// Tool calls and reasoning from a session file, shown collapsed under the
+// message they followed
+export function TurnItemList({ items }: TurnItemListProps) {
+  if (items.length === 0) return null;
+
+  return (
+    <div className="mb-2 space-y-0.5 min-w-0">
+      {items.map((item, index) => (
+        <TurnItemRow key={'callId' in item ? `${item.callId}-${index}` : index} item={item} />
+      ))}
+    </div>
+  );
+}
@@ -1,11 +1,11 @@
 import React from 'react';
 import { Button } from '../ui/button';
 import { AlertTriangle } from 'lucide-react';
-import { ApprovalRequest } from '@/types/codex';
+import { ApprovalRequest, ReviewDecision } from '@/types/codex';
 
 interface ApprovalDialogProps {
   pendingApproval: ApprovalRequest | null;
-  onApproval: (approved: boolean) => void;
+  onApproval: (decision: ReviewDecision) => void;
 }
 
 export const ApprovalDialog: React.FC<ApprovalDialogProps> = ({ 
@@ -44,19 +44,33 @@ export const ApprovalDialog: React.FC<ApprovalDialogProps> = ({
           )}
         </div>
         <div className="flex gap-2">
+          <Button
+            size="sm"
+            variant="outline"
+            onClick={() => onApproval('abort')}
+          >
+            Abort
+          </Button>
           <Button
             size="sm"
             variant="destructive"
-            onClick={() => onApproval(false)}
+            onClick={() => onApproval('denied')}
           >
             Deny
           </Button>
           <Button
             size="sm"
-            onClick={() => onApproval(true)}
+            onClick={() => onApproval('approved')}
           >
             Allow
           </Button>
+          <Button
+            size="sm"
+            variant="secondary"
+            onClick={() => onApproval('approved_for_session')}
+          >
+            Always allow this session
+          </Button>
         </div>
       </div>
     </div>
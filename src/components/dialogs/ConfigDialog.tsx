@@ -2,8 +2,9 @@ import React, { useState, useEffect } from 'react';
 import { open } from '@tauri-apps/plugin-dialog';
 import { Button } from '../ui/button';
 import { Input } from '../ui/input';
+import { Textarea } from '../ui/textarea';
 import { Settings, Folder, FileText } from 'lucide-react';
-import { CodexConfig, DEFAULT_CONFIG } from '@/types/codex';
+import { CodexConfig, DEFAULT_CONFIG, SessionSettings } from '@/types/codex';
 import {
   Dialog,
   DialogContent,
@@ -76,6 +77,13 @@ export const ConfigDialog: React.FC<ConfigDialogProps> = ({
     }
   };
 
+  const updateSessionSettings = <K extends keyof SessionSettings>(key: K, value: SessionSettings[K]) => {
+    setLocalConfig(prev => ({
+      ...prev,
+      sessionSettings: { ...prev.sessionSettings, [key]: value },
+    }));
+  };
+
   const handleSave = () => {
     onSave(localConfig);
     onClose();
@@ -200,6 +208,54 @@ export const ConfigDialog: React.FC<ConfigDialogProps> = ({
             </div>
           </div>
 
+          {/* Session Settings */}
+          <div className="space-y-4">
+            <h3 className="text-lg font-medium">Session Settings</h3>
+
+            <div className="space-y-2">
+              <label className="text-sm font-medium">Reasoning Effort</label>
+              <div className="grid grid-cols-4 gap-2">
+                {(['minimal', 'low', 'medium', 'high'] as const).map((effort) => (
+                  <Button
+                    key={effort}
+                    variant={(localConfig.sessionSettings?.reasoningEffort ?? 'medium') === effort ? 'default' : 'outline'}
+                    size="sm"
+                    onClick={() => updateSessionSettings('reasoningEffort', effort)}
+                  >
+                    {effort.charAt(0).toUpperCase() + effort.slice(1)}
+                  </Button>
+                ))}
+              </div>
+            </div>
+
+            <div className="space-y-2">
+              <label className="text-sm font-medium">Custom Instructions</label>
+              <Textarea
+                value={localConfig.sessionSettings?.userInstructions || ''}
+                onChange={(e) => updateSessionSettings('userInstructions', e.target.value)}
+                placeholder="Extra instructions for this session"
+                className="min-h-[60px]"
+              />
+            </div>
+
+            {localConfig.sandboxMode === 'workspace-write' && (
+              <div className="space-y-2">
+                <label className="text-sm font-medium">Additional Writable Roots</label>
+                <Input
+                  defaultValue={localConfig.sessionSettings?.writableRoots?.join(', ') || ''}
+                  onBlur={(e) => updateSessionSettings(
+                    'writableRoots',
+                    e.target.value.split(',').map(root => root.trim()).filter(Boolean)
+                  )}
+                  placeholder="/tmp, /home/me/shared"
+                />
+                <p className="text-xs text-gray-500">
+                  Comma separated directories codex may write to besides the working directory
+                </p>
+              </div>
+            )}
+          </div>
+
           {/* Custom Arguments */}
           <div className="space-y-2">
             <label className="text-sm font-medium">Custom Arguments (Advanced)</label>
@@ -9,16 +9,17 @@ interface FormData {
   port: string
   username: string
   keyPath: string
+  auth: "key" | "agent" | "password"
 }
 
 interface CreateInstanceDialogProps {
   open: boolean
   onOpenChange: (open: boolean) => void
-  onCreate: (data: { name: string; type: "local" | "ssh"; host?: string; port?: number; username?: string; keyPath?: string }) => void
+  onCreate: (data: { name: string; type: "local" | "ssh"; host?: string; port?: number; username?: string; keyPath?: string; auth?: "key" | "agent" | "password" }) => void
 }
 
 export function CreateInstanceDialog({ open, onOpenChange, onCreate }: CreateInstanceDialogProps) {
-  const [form, setForm] = useState<FormData>({ name: "", host: "", port: "22", username: "", keyPath: "" })
+  const [form, setForm] = useState<FormData>({ name: "", host: "", port: "22", username: "", keyPath: "", auth: "key" })
 
   const handleChange = (field: keyof FormData, value: string) => {
     setForm(prev => ({ ...prev, [field]: value }))
@@ -32,9 +33,10 @@ export function CreateInstanceDialog({ open, onOpenChange, onCreate }: CreateIns
       host: form.host || undefined,
       port: form.host ? Number(form.port) : undefined,
       username: form.host ? form.username : undefined,
-      keyPath: form.host ? form.keyPath : undefined
+      keyPath: form.host && form.auth === "key" ? form.keyPath : undefined,
+      auth: form.host ? form.auth : undefined
     })
-    setForm({ name: "", host: "", port: "22", username: "", keyPath: "" })
+    setForm({ name: "", host: "", port: "22", username: "", keyPath: "", auth: "key" })
     onOpenChange(false)
   }
 
@@ -49,7 +51,18 @@ export function CreateInstanceDialog({ open, onOpenChange, onCreate }: CreateIns
           <Input placeholder="Host" value={form.host} onChange={e => handleChange("host", e.target.value)} />
           <Input placeholder="Port" value={form.port} onChange={e => handleChange("port", e.target.value)} />
           <Input placeholder="Username" value={form.username} onChange={e => handleChange("username", e.target.value)} />
-          <Input placeholder="Key Path" value={form.keyPath} onChange={e => handleChange("keyPath", e.target.value)} />
+          <select
+            className="w-full h-9 rounded-md border bg-transparent px-3 text-sm"
+            value={form.auth}
+            onChange={e => handleChange("auth", e.target.value)}
+          >
+            <option value="key">Key file</option>
+            <option value="agent">SSH agent</option>
+            <option value="password">Password</option>
+          </select>
+          {form.auth === "key" && (
+            <Input placeholder="Key Path" value={form.keyPath} onChange={e => handleChange("keyPath", e.target.value)} />
+          )}
         </div>
         <div className="flex justify-end gap-2 pt-4">
           <Button variant="outline" onClick={() => onOpenChange(false)}>Cancel</Button>
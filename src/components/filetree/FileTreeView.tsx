@@ -6,6 +6,7 @@ import { useContextFilesStore } from "@/stores/ContextFilesStore";
 import { useFileTokens } from "@/hooks/useFileTokens";
 import { FileTreeHeader } from "./FileTreeHeader";
 import { FileTreeItem } from "./FileTreeItem";
+import { sessionManager } from "@/services/sessionManager";
 
 interface FileEntry {
   name: string;
@@ -53,6 +54,7 @@ export function FileTree({
 
       const result = await invoke<FileEntry[]>("read_directory", {
         path: targetPath,
+        sessionId: sessionManager.getActiveBackendSessionId(),
       });
       setEntries(result);
     } catch (err) {
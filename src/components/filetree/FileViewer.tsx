@@ -7,6 +7,7 @@ import { DiffViewer } from "./DiffViewer";
 import { useEditorStore } from "@/stores/EditorStore";
 import { useConversationStore } from "@/stores/ConversationStore";
 import { useInstanceStore } from "@/stores/InstanceStore";
+import { sessionManager } from "@/services/sessionManager";
 
 interface FileViewerProps {
   filePath: string | null;
@@ -83,7 +84,10 @@ export function FileViewer({ filePath, onClose, addToNotepad }: FileViewerProps)
             });
             break;
           default:
-            fileContent = await invoke<string>("read_file", { filePath });
+            fileContent = await invoke<string>("read_file", {
+              filePath,
+              sessionId: sessionManager.getActiveBackendSessionId(),
+            });
             break;
         }
 
@@ -108,7 +112,10 @@ export function FileViewer({ filePath, onClose, addToNotepad }: FileViewerProps)
     
     setDiffLoading(true);
     try {
-      const diff = await invoke<GitDiff>("get_git_file_diff", { filePath });
+      const diff = await invoke<GitDiff>("get_git_file_diff", {
+        filePath,
+        sessionId: sessionManager.getActiveBackendSessionId(),
+      });
       setGitDiff(diff);
     } catch (err) {
       console.error("Failed to load git diff:", err);
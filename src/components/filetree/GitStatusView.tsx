@@ -3,6 +3,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { useFolderStore } from "@/stores/FolderStore";
 import { RefreshCw, GitBranch, FileText } from "lucide-react";
 import { Button } from "@/components/ui/button";
+import { sessionManager } from "@/services/sessionManager";
 
 interface GitStatus {
 	staged: string[];
@@ -43,6 +44,7 @@ export function GitStatusView({ currentFolder, onDiffClick }: GitStatusViewProps
 
 			const result = await invoke<GitStatus>("get_git_status", {
 				directory: targetPath,
+				sessionId: sessionManager.getActiveBackendSessionId(),
 			});
 			setGitStatus(result);
 		} catch (err) {
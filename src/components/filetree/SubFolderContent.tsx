@@ -1,6 +1,7 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { FileTreeItem } from "./FileTreeItem";
+import { sessionManager } from "@/services/sessionManager";
 
 interface FileEntry {
   name: string;
@@ -42,6 +43,7 @@ export function SubFolderContent({
       try {
         const result = await invoke<FileEntry[]>("read_directory", {
           path: folderPath,
+          sessionId: sessionManager.getActiveBackendSessionId(),
         });
         setSubEntries(result);
       } catch (err) {
@@ -1,9 +1,22 @@
+import { useEffect } from "react";
 import { Outlet } from "react-router-dom";
+import { listen } from "@tauri-apps/api/event";
 import { AppHeader } from "./AppHeader";
 import { RouteTracker } from "../common/RouteTracker";
-import { Toaster } from 'sonner';
+import { Toaster, toast } from 'sonner';
+import type { CodexVersionWarningEvent } from "@/types/codex";
 
 export function Layout() {
+  // Sessions may start from any view, so remote version warnings are shown here
+  useEffect(() => {
+    const unlisten = listen<CodexVersionWarningEvent>("codex-version-warning", (event) => {
+      toast.warning(`${event.payload.host}: ${event.payload.message}`);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   return (
     <main className="h-screen flex flex-col">
       <RouteTracker />
@@ -1,26 +1,30 @@
 import { useEffect, useRef, useCallback } from 'react';
 import { listen } from '@tauri-apps/api/event';
-import { ChatMessage, CodexEvent, ApprovalRequest } from '@/types/codex';
+import { ChatMessage, CodexEvent, SessionEvent, ApprovalRequest, FileChange, AutoApprovalEvent, ConnectionStateEvent, SessionSettingsChangedEvent, SessionTerminatedEvent } from '@/types/codex';
 import { useConversationStore } from '../stores/ConversationStore';
 import { useInstanceStore } from '../stores/InstanceStore';
 import { StreamController, StreamControllerSink } from '@/utils/streamController';
+import { sessionManager } from '@/services/sessionManager';
+
+// Approval text for one file of a patch, so additions and deletions are
+// shown as well as edits
+const describeFileChange = (path: string, change: FileChange): string => {
+  if ('add' in change) {
+    const lines = change.add.content.replace(/\n$/, '').split('\n');
+    return [`--- /dev/null`, `+++ ${path}`, ...lines.map((line) => `+${line}`)].join('\n');
+  }
+  if ('delete' in change) {
+    return `deleted ${path}`;
+  }
+  const moved = change.update.move_path ? `moved ${path} -> ${change.update.move_path}\n` : '';
+  return moved + change.update.unified_diff;
+};
 
 interface UseCodexEventsProps {
   sessionId: string;
   onApprovalRequest: (request: ApprovalRequest) => void;
 }
 
-// Helper function to extract session ID from codex events
-const getEventSessionId = (event: CodexEvent): string | null => {
-  const { msg } = event;
-  switch (msg.type) {
-    case 'session_configured':
-      return msg.session_id;
-    default:
-      return null; // For other events, we can't determine session ID, so process them
-  }
-};
-
 export const useCodexEvents = ({ 
   sessionId, 
   onApprovalRequest
@@ -139,21 +143,34 @@ export const useCodexEvents = ({
         }
         break;
         
+      case 'agent_reasoning':
+        addMessageToStore({
+          id: `${sessionId}-reasoning-${Date.now()}-${Math.random().toString(36).substring(2, 11)}`,
+          type: 'system',
+          content: msg.text,
+          timestamp: new Date(),
+        });
+        break;
+
       case 'exec_approval_request':
         onApprovalRequest({
           id: event.id,
+          callId: msg.call_id,
           type: 'exec',
-          command: msg.command,
+          command: msg.command.join(' '),
           cwd: msg.cwd,
         });
         break;
         
-      case 'patch_approval_request':
+      case 'apply_patch_approval_request':
         onApprovalRequest({
           id: event.id,
+          callId: msg.call_id,
           type: 'patch',
-          patch: msg.patch,
-          files: msg.files,
+          patch: Object.entries(msg.changes)
+            .map(([path, change]) => describeFileChange(path, change))
+            .join('\n'),
+          files: Object.keys(msg.changes),
         });
         break;
         
@@ -190,6 +207,57 @@ export const useCodexEvents = ({
       case 'exec_command_end':
         console.log('Command execution completed');
         break;
+
+      case 'mcp_tool_call_end': {
+        const { server, tool } = msg.invocation;
+        addMessageToStore({
+          id: `${sessionId}-tool-${Date.now()}-${Math.random().toString(36).substring(2, 11)}`,
+          type: 'system',
+          content: 'Err' in msg.result
+            ? `Tool ${server}.${tool} failed: ${msg.result.Err}`
+            : `Called tool ${server}.${tool}`,
+          timestamp: new Date(),
+        });
+        break;
+      }
+
+      case 'patch_apply_end':
+        addMessageToStore({
+          id: `${sessionId}-patch-${Date.now()}-${Math.random().toString(36).substring(2, 11)}`,
+          type: 'system',
+          content: msg.success ? msg.stdout.trim() : `Patch failed: ${msg.stderr.trim()}`,
+          timestamp: new Date(),
+        });
+        break;
+
+      case 'plan_update':
+        addMessageToStore({
+          id: `${sessionId}-plan-${Date.now()}-${Math.random().toString(36).substring(2, 11)}`,
+          type: 'system',
+          content: msg.plan
+            .map((item) => `${item.status === 'completed' ? '[x]' : '[ ]'} ${item.step}`)
+            .join('\n'),
+          timestamp: new Date(),
+        });
+        break;
+
+      case 'stream_error':
+        console.warn('Stream error:', msg.message);
+        break;
+
+      case 'token_count':
+      case 'agent_reasoning_delta':
+      case 'agent_reasoning_raw_content':
+      case 'agent_reasoning_raw_content_delta':
+      case 'agent_reasoning_section_break':
+      case 'mcp_tool_call_begin':
+      case 'web_search_begin':
+      case 'web_search_end':
+      case 'patch_apply_begin':
+      case 'turn_diff':
+      case 'get_history_entry_response':
+        // Handled elsewhere or intentionally ignored
+        break;
         
       default:
         console.log('Unhandled event type:', msg.type);
@@ -199,26 +267,90 @@ export const useCodexEvents = ({
   useEffect(() => {
     if (!sessionId) return;
 
-    // Listen to the global codex-events channel
-    const eventUnlisten = listen<CodexEvent>("codex-events", (event) => {
-      const codexEvent = event.payload;
-      
-      // Check if this event is for our session
-      const eventSessionId = getEventSessionId(codexEvent);
-      const ourSessionId = sessionId.replace('codex-event-', '');
-      
-      if (eventSessionId && eventSessionId !== ourSessionId) {
-        // This event is for a different session, ignore it
-        return;
-      }
-      
+    const rawSessionId = sessionId.replace('codex-event-', '');
+
+    // Each backend session emits on its own channel
+    const eventUnlisten = listen<SessionEvent>(`codex-events:${rawSessionId}`, (event) => {
+      const { session_id, ...codexEvent } = event.payload;
+      if (session_id !== rawSessionId) return;
+
       console.log(`Received codex event for session ${sessionId}:`, codexEvent);
       handleCodexEvent(codexEvent);
     });
     
+    const connectionUnlisten = listen<ConnectionStateEvent>("connection-state", (event) => {
+      const { session_id, ...state } = event.payload;
+      if (session_id !== rawSessionId) return;
+
+      let content: string | null = null;
+      if (state.state === 'reconnecting' && state.attempt === 1) {
+        content = 'Connection lost, reconnecting...';
+      } else if (state.state === 'connected') {
+        content = 'Reconnected.';
+      } else if (state.state === 'disconnected') {
+        content = `Disconnected: ${state.reason}`;
+        setSessionLoading(sessionId, false);
+      }
+      if (content) {
+        addMessageToStore({
+          id: `${sessionId}-connection-${Date.now()}-${Math.random().toString(36).substring(2, 11)}`,
+          type: 'system',
+          content,
+          timestamp: new Date(),
+        });
+      }
+    });
+    
+    const terminatedUnlisten = listen<SessionTerminatedEvent>("session-terminated", (event) => {
+      const { session_id, exit_code, signal, stderr_tail } = event.payload;
+      if (session_id !== rawSessionId) return;
+
+      const status = exit_code !== null ? `exit code ${exit_code}` : signal !== null ? `signal ${signal}` : 'unknown status';
+      const details = stderr_tail.length ? `\n\n\`\`\`\n${stderr_tail.join('\n')}\n\`\`\`` : '';
+      addMessageToStore({
+        id: `${sessionId}-terminated-${Date.now()}`,
+        type: 'system',
+        content: `Codex process exited (${status}).${details}`,
+        timestamp: new Date(),
+      });
+      setSessionLoading(sessionId, false);
+      sessionManager.forgetSession(sessionId);
+    });
+    
+    const autoApprovalUnlisten = listen<AutoApprovalEvent>("auto-approval", (event) => {
+      const { session_id, command, decision } = event.payload;
+      if (session_id !== rawSessionId) return;
+
+      addMessageToStore({
+        id: `${sessionId}-auto-approval-${Date.now()}-${Math.random().toString(36).substring(2, 11)}`,
+        type: 'system',
+        content: `${decision === 'denied' ? 'Auto-denied' : 'Auto-approved'} by project rule: \`${command.join(' ')}\``,
+        timestamp: new Date(),
+      });
+    });
+    
+    const settingsUnlisten = listen<SessionSettingsChangedEvent>("session-settings-changed", (event) => {
+      const { session_id, changes } = event.payload;
+      if (session_id !== rawSessionId) return;
+
+      const summary = changes
+        .map(({ setting, from, to }) => `${setting}: ${JSON.stringify(from)} → ${JSON.stringify(to)}`)
+        .join('\n');
+      addMessageToStore({
+        id: `${sessionId}-settings-${Date.now()}-${Math.random().toString(36).substring(2, 11)}`,
+        type: 'system',
+        content: `Session settings changed:\n${summary}`,
+        timestamp: new Date(),
+      });
+    });
+    
     // Cleanup function
     return () => {
       eventUnlisten.then(fn => fn());
+      connectionUnlisten.then(fn => fn());
+      terminatedUnlisten.then(fn => fn());
+      autoApprovalUnlisten.then(fn => fn());
+      settingsUnlisten.then(fn => fn());
       // Clear streaming state when component unmounts or sessionId changes
       streamController.current.clearAll();
       currentStreamingMessageId.current = null;
@@ -14,6 +14,7 @@ import { useInstanceStore } from "@/stores/InstanceStore";
 import { Tabs, TabsList, TabsTrigger, TabsContent } from "@/components/ui/tabs";
 import { invoke } from "@tauri-apps/api/core";
 import { GitBranch } from "lucide-react";
+import { sessionManager } from "@/services/sessionManager";
 
 export default function ChatPage() {
 
@@ -47,7 +48,8 @@ export default function ChatPage() {
       console.log('Trying full path:', fullPath);
       
       const result = await invoke<{ original_content: string; current_content: string; has_changes: boolean }>("get_git_file_diff", {
-        filePath: fullPath
+        filePath: fullPath,
+        sessionId: sessionManager.getActiveBackendSessionId(),
       });
       
       if (result.has_changes) {
@@ -200,6 +200,12 @@ export default function UsagePage() {
         </Card>
       </div>
 
+      {usageData.untrackedSessions > 0 && (
+        <p className="text-sm text-muted-foreground">
+          {formatNumber(usageData.untrackedSessions)} older sessions recorded no token usage and are not counted.
+        </p>
+      )}
+
       {/* Detailed Views */}
       <Tabs defaultValue="overview" className="space-y-4">
         <TabsList>
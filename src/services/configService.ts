@@ -1,5 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
-import { ModelProvider, Profile, ProviderConfig } from '@/types/config';
+import { ApprovalRule, ConnectionProfile, ModelProvider, PriceTable, Profile, ProviderConfig } from '@/types/config';
 
 export class ConfigService {
   static async getProviderConfig(providerName: string): Promise<ProviderConfig | null> {
@@ -118,4 +118,81 @@ export class ConfigService {
       throw new Error(`Failed to add/update model provider: ${error}`);
     }
   }
-}
\ No newline at end of file
+
+  static async getConnectionProfiles(): Promise<Record<string, ConnectionProfile>> {
+    try {
+      return await invoke<Record<string, ConnectionProfile>>('read_connection_profiles');
+    } catch (error) {
+      console.error('Failed to get connection profiles:', error);
+      return {};
+    }
+  }
+
+  static async addOrUpdateConnectionProfile(profileName: string, profile: ConnectionProfile): Promise<void> {
+    try {
+      await invoke('add_or_update_connection_profile', {
+        profileName,
+        profile
+      });
+    } catch (error) {
+      console.error(`Failed to add/update connection profile ${profileName}:`, error);
+      throw new Error(`Failed to add/update connection profile: ${error}`);
+    }
+  }
+
+  static async deleteConnectionProfile(profileName: string): Promise<void> {
+    try {
+      await invoke('delete_connection_profile', {
+        profileName
+      });
+    } catch (error) {
+      console.error(`Failed to delete connection profile ${profileName}:`, error);
+      throw new Error(`Failed to delete connection profile: ${error}`);
+    }
+  }
+
+  static async importSshConfigHosts(): Promise<Record<string, ConnectionProfile>> {
+    try {
+      return await invoke<Record<string, ConnectionProfile>>('import_ssh_config_hosts');
+    } catch (error) {
+      console.error('Failed to import ~/.ssh/config hosts:', error);
+      return {};
+    }
+  }
+
+  static async getApprovalRules(projectPath: string): Promise<ApprovalRule[]> {
+    try {
+      return await invoke<ApprovalRule[]>('read_approval_rules', { projectPath });
+    } catch (error) {
+      console.error(`Failed to get approval rules for ${projectPath}:`, error);
+      return [];
+    }
+  }
+
+  static async saveApprovalRules(projectPath: string, rules: ApprovalRule[]): Promise<void> {
+    try {
+      await invoke('save_approval_rules', { projectPath, rules });
+    } catch (error) {
+      console.error(`Failed to save approval rules for ${projectPath}:`, error);
+      throw new Error(`Failed to save approval rules: ${error}`);
+    }
+  }
+
+  static async getModelPrices(): Promise<PriceTable> {
+    try {
+      return await invoke<PriceTable>('read_model_prices');
+    } catch (error) {
+      console.error('Failed to get model prices:', error);
+      return { models: {} };
+    }
+  }
+
+  static async saveModelPrices(prices: PriceTable): Promise<void> {
+    try {
+      await invoke('save_model_prices', { prices });
+    } catch (error) {
+      console.error('Failed to save model prices:', error);
+      throw new Error(`Failed to save model prices: ${error}`);
+    }
+  }
+}
@@ -1,6 +1,12 @@
 import { invoke } from '@tauri-apps/api/core';
 import { useConversationStore } from '@/stores/ConversationStore';
-import type { Conversation } from '@/types/chat';
+import type { Conversation, SessionCleanup, SessionSearchHit } from '@/types/chat';
+
+interface SessionPage {
+  conversations: Conversation[];
+  total: number;
+  nextOffset: number | null;
+}
 
 class SessionLoaderService {
   async loadSessionsFromDisk(): Promise<Conversation[]> {
@@ -15,6 +21,19 @@ class SessionLoaderService {
     }
   }
 
+  // Loads history newest first, handing each page over as soon as it arrives
+  async loadSessionsInPages(
+    onPage: (conversations: Conversation[], total: number) => void,
+    pageSize = 50,
+  ): Promise<void> {
+    let offset: number | null = 0;
+    while (offset !== null) {
+      const page: SessionPage = await invoke<SessionPage>('load_sessions_page', { offset, limit: pageSize });
+      onPage(page.conversations, page.total);
+      offset = page.nextOffset;
+    }
+  }
+
   async toggleFavorite(conversationId: string): Promise<void> {
     try {
       const { conversations, toggleFavorite } = useConversationStore.getState();
@@ -45,6 +64,21 @@ class SessionLoaderService {
     }
   }
 
+  async searchSessions(query: string, limit?: number): Promise<SessionSearchHit[]> {
+    try {
+      return await invoke<SessionSearchHit[]>('search_sessions', { query, limit });
+    } catch (error) {
+      console.error('Error searching sessions:', error);
+      return [];
+    }
+  }
+
+  // Moves rollouts holding only session metadata to the trash; a dry run
+  // only lists them
+  async cleanUpSessions(dryRun: boolean, minAgeMinutes?: number): Promise<SessionCleanup> {
+    return await invoke<SessionCleanup>('clean_up_sessions', { dryRun, minAgeMinutes });
+  }
+
   async isConversationFavorited(conversationId: string): Promise<boolean> {
     const { conversations } = useConversationStore.getState();
     return conversations.some(c => c.id === conversationId && c.isFavorite);
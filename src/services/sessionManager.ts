@@ -10,7 +10,40 @@ class SessionManager {
   private runningSessions: Set<string> = new Set();
   private readonly MAX_CONCURRENT_SESSIONS = 2; // Limit concurrent sessions
 
-  async ensureSessionRunning(sessionId: string, config: CodexConfig): Promise<void> {
+  // Config in the shape the backend's CodexConfig expects, minus the connection
+  private toBackendConfig(config: CodexConfig): any {
+    // Get current folder
+    const currentFolder = useFolderStore.getState().currentFolder;
+    
+    console.log(`📁 currentFolder: ${currentFolder})`);
+    
+    // Get API key from settings store
+    const settingsStore = useSettingsStore.getState();
+    const providerConfig = settingsStore.providers[config.provider as keyof typeof settingsStore.providers];
+    const apiKey = providerConfig?.apiKey || null;
+    
+    console.log(`🔑 API key debug - Provider: ${config.provider}, Has API key: ${!!apiKey}, Length: ${apiKey?.length || 0}`);
+
+    return {
+      working_directory: currentFolder,
+      model: config.model,
+      provider: config.provider,
+      use_oss: config.useOss,
+      custom_args: config.customArgs || null,
+      approval_policy: config.approvalPolicy,
+      sandbox_mode: config.sandboxMode,
+      api_key: apiKey,
+      session_settings: {
+        reasoning_effort: config.sessionSettings?.reasoningEffort ?? null,
+        user_instructions: config.sessionSettings?.userInstructions || null,
+        writable_roots: config.sessionSettings?.writableRoots ?? [],
+        network_access: config.sessionSettings?.networkAccess ?? false,
+      },
+    };
+  }
+
+  // resumePath continues the conversation recorded in that rollout file
+  async ensureSessionRunning(sessionId: string, config: CodexConfig, resumePath?: string): Promise<void> {
     // If session is already running, do nothing
     if (this.runningSessions.has(sessionId)) {
       return;
@@ -31,21 +64,10 @@ class SessionManager {
       
       console.log(`🚀 Starting backend session: ${rawSessionId} (from frontend: ${sessionId})`);
 
-      // Get current folder
-      const currentFolder = useFolderStore.getState().currentFolder;
-      
-      console.log(`📁 currentFolder: ${currentFolder})`);
-
-      // Get API key from settings store
-      const settingsStore = useSettingsStore.getState();
-      const providerConfig = settingsStore.providers[config.provider as keyof typeof settingsStore.providers];
-      const apiKey = providerConfig?.apiKey || null;
-
-      console.log(`🔑 API key debug - Provider: ${config.provider}, Has API key: ${!!apiKey}, Length: ${apiKey?.length || 0}`);
-
       const conv = useConversationStore.getState().conversations.find(c => c.id === sessionId);
       const inst = conv ? useInstanceStore.getState().instances.find(i => i.id === conv.instanceId) : undefined;
       let connection: any = undefined;
+      let password: string | null = null;
       if (inst && inst.type === 'ssh') {
         connection = {
           type: 'ssh',
@@ -53,23 +75,29 @@ class SessionManager {
           user: inst.username || '',
           port: inst.port,
           keyPath: inst.keyPath,
+          auth: inst.auth || 'key',
         };
+        if (connection.auth === 'password') {
+          // Only kept for this call; the backend hands it to the askpass helper
+          password = window.prompt(`Password for ${connection.user ? `${connection.user}@` : ''}${connection.host}`);
+          if (!password) throw new Error('SSH password required');
+        }
       }
-      const cfg: any = {
-        working_directory: currentFolder,
-        model: config.model,
-        provider: config.provider,
-        use_oss: config.useOss,
-        custom_args: config.customArgs || null,
-        approval_policy: config.approvalPolicy,
-        sandbox_mode: config.sandboxMode,
-        api_key: apiKey,
-      };
+      const cfg: any = this.toBackendConfig(config);
       if (connection) cfg.connection = connection;
-      await invoke('start_codex_session', {
-        sessionId: rawSessionId,
-        config: cfg,
-      });
+      if (resumePath) {
+        await invoke<string>('resume_codex_session', {
+          filePath: resumePath,
+          config: cfg,
+          password,
+        });
+      } else {
+        await invoke('start_codex_session', {
+          sessionId: rawSessionId,
+          config: cfg,
+          password,
+        });
+      }
 
       this.runningSessions.add(sessionId);
       this.sessionConfigs.set(sessionId, config);
@@ -142,6 +170,31 @@ class SessionManager {
     }
   }
 
+  // Switch a running session to new settings in place. The backend respawns
+  // codex on the session's rollout file, so the conversation carries on.
+  async updateSessionSettings(sessionId: string, config: CodexConfig): Promise<void> {
+    const previous = this.sessionConfigs.get(sessionId);
+    if (!previous || JSON.stringify(previous) === JSON.stringify(config)) {
+      return;
+    }
+
+    const rawSessionId = sessionId.startsWith('codex-event-') 
+      ? sessionId.replace('codex-event-', '') 
+      : sessionId;
+
+    await invoke('update_session_settings', {
+      sessionId: rawSessionId,
+      config: this.toBackendConfig(config),
+    });
+    this.sessionConfigs.set(sessionId, config);
+  }
+
+  // Drop local state for a session whose backend process already exited
+  forgetSession(sessionId: string): void {
+    this.sessionConfigs.delete(sessionId);
+    this.runningSessions.delete(sessionId);
+  }
+
   isSessionRunning(sessionId: string): boolean {
     return this.runningSessions.has(sessionId);
   }
@@ -164,6 +217,14 @@ class SessionManager {
   }
 
 
+  // Backend id of the current conversation's running session, so file panes
+  // can read from the same (possibly remote) machine the session runs on
+  getActiveBackendSessionId(): string | undefined {
+    const sessionId = useConversationStore.getState().currentConversationId;
+    if (!sessionId || !this.runningSessions.has(sessionId)) return undefined;
+    return sessionId.replace('codex-event-', '');
+  }
+
   getSessionConfig(sessionId: string): CodexConfig | undefined {
     return this.sessionConfigs.get(sessionId);
   }
@@ -12,6 +12,7 @@ export interface Instance {
   port?: number
   username?: string
   keyPath?: string
+  auth?: "key" | "agent" | "password"
 }
 
 interface InstanceState {
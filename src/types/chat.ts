@@ -7,8 +7,45 @@ export interface ChatMessage {
   isStreaming?: boolean;
   model?: string;
   workingDirectory?: string;
+  // Tool calls and reasoning recorded after this message in a session file
+  items?: TurnItem[];
 }
 
+export type TurnItem =
+  | {
+      type: "exec";
+      callId: string;
+      command: string[];
+      output?: string | null;
+      exitCode?: number | null;
+      durationSeconds?: number | null;
+    }
+  | {
+      type: "patch";
+      callId: string;
+      patch: string;
+      files: string[];
+      output?: string | null;
+      success?: boolean | null;
+    }
+  | { type: "reasoning"; summary: string }
+  | {
+      type: "mcp_tool_call";
+      callId: string;
+      server: string;
+      tool: string;
+      arguments: unknown;
+      output?: string | null;
+      success?: boolean | null;
+    }
+  | {
+      type: "tool_call";
+      callId: string;
+      name: string;
+      arguments: string;
+      output?: string | null;
+    };
+
 export type ChatMode = "chat" | "agent";
 
 export interface Conversation {
@@ -24,6 +61,42 @@ export interface Conversation {
   instanceId?: string;
 }
 
+// Emitted when a session file that was already listed gains messages
+export interface SessionUpdatedEvent {
+  id: string;
+  title: string;
+  updatedAt: number;
+  filePath: string | null;
+  // Messages before this index are unchanged
+  firstMessageIndex: number;
+  messages: ChatMessage[];
+}
+
+export interface SessionDeletedEvent {
+  id: string;
+  filePath: string | null;
+}
+
+// Full-text hit from the backend's index of all session files
+export interface SessionSearchHit {
+  session_id: string;
+  file_path: string;
+  kind: "message" | "command" | "file";
+  message_index: number | null;
+  // Matched terms are wrapped in « »
+  snippet: string;
+  score: number;
+}
+
+// Result of the backend's cleanup of metadata-only session files
+export interface SessionCleanup {
+  dryRun: boolean;
+  files: string[];
+  skippedRecent: number;
+  errors: { file: string; error: string }[];
+  trashDir: string;
+}
+
 export interface ChatRequest {
   message: string;
   provider: string;
@@ -1,23 +1,99 @@
+import type { TurnItem } from "./chat";
+
 export interface CodexEvent {
   id: string;
   msg: EventMsg;
 }
 
+export type SessionEvent = CodexEvent & { session_id: string };
+
 export type EventMsg = 
-  | { type: 'session_configured'; session_id: string; model: string; history_log_id?: number; history_entry_count?: number }
+  | { type: 'session_configured'; session_id: string; model: string; history_log_id?: number; history_entry_count?: number; rollout_path?: string }
   | { type: 'task_started' }
   | { type: 'task_complete'; response_id?: string; last_agent_message?: string }
   | { type: 'agent_message'; message?: string; last_agent_message?: string }
   | { type: 'agent_message_delta'; delta: string }
-  | { type: 'exec_approval_request'; command: string; cwd: string }
-  | { type: 'patch_approval_request'; patch: string; files: string[] }
+  | { type: 'agent_reasoning'; text: string }
+  | { type: 'agent_reasoning_delta'; delta: string }
+  | { type: 'agent_reasoning_raw_content'; text: string }
+  | { type: 'agent_reasoning_raw_content_delta'; delta: string }
+  | { type: 'agent_reasoning_section_break' }
+  | { type: 'token_count'; info: TokenUsageInfo | null }
+  | { type: 'exec_approval_request'; call_id: string; command: string[]; cwd: string; reason?: string }
+  | { type: 'apply_patch_approval_request'; call_id: string; changes: Record<string, FileChange>; reason?: string; grant_root?: string }
   | { type: 'error'; message: string }
   | { type: 'turn_complete'; response_id?: string }
   | { type: 'exec_command_begin'; call_id: string; command: string[]; cwd: string }
   | { type: 'exec_command_output_delta'; call_id: string; stream: string; chunk: number[] }
   | { type: 'exec_command_end'; call_id: string; stdout: string; stderr: string; exit_code: number }
+  | { type: 'mcp_tool_call_begin'; call_id: string; invocation: McpInvocation }
+  | { type: 'mcp_tool_call_end'; call_id: string; invocation: McpInvocation; duration: { secs: number; nanos: number }; result: { Ok: unknown } | { Err: string } }
+  | { type: 'web_search_begin'; call_id: string }
+  | { type: 'web_search_end'; call_id: string; query: string }
+  | { type: 'patch_apply_begin'; call_id: string; auto_approved: boolean; changes: Record<string, FileChange> }
+  | { type: 'patch_apply_end'; call_id: string; stdout: string; stderr: string; success: boolean }
+  | { type: 'turn_diff'; unified_diff: string }
+  | { type: 'plan_update'; explanation?: string; plan: PlanItem[] }
+  | { type: 'get_history_entry_response'; offset: number; log_id: number; entry?: { session_id: string; ts: number; text: string } }
   | { type: 'shutdown_complete' }
-  | { type: 'background_event'; message: string };
+  | { type: 'background_event'; message: string }
+  | { type: 'stream_error'; message: string };
+
+export interface TokenUsage {
+  input_tokens: number;
+  cached_input_tokens: number;
+  output_tokens: number;
+  reasoning_output_tokens: number;
+  total_tokens: number;
+}
+
+export interface TokenUsageInfo {
+  total_token_usage: TokenUsage;
+  last_token_usage: TokenUsage;
+  model_context_window?: number;
+}
+
+export interface McpInvocation {
+  server: string;
+  tool: string;
+  arguments?: unknown;
+}
+
+export type FileChange =
+  | { add: { content: string } }
+  | { delete: { content?: string } }
+  | { update: { unified_diff: string; move_path?: string } };
+
+export interface PlanItem {
+  step: string;
+  status: 'pending' | 'in_progress' | 'completed';
+}
+
+export type ConnectionState =
+  | { state: 'connected' }
+  | { state: 'reconnecting'; attempt: number }
+  | { state: 'disconnected'; reason: string };
+
+export type ConnectionStateEvent = { session_id: string } & ConnectionState;
+
+export interface SessionTerminatedEvent {
+  session_id: string;
+  exit_code: number | null;
+  signal: number | null;
+  stderr_tail: string[];
+}
+
+// Emitted before a remote session starts on a different codex version
+export interface CodexVersionWarningEvent {
+  session_id: string;
+  host: string;
+  message: string;
+}
+
+export type Attachment =
+  | { type: 'image'; path: string }
+  | { type: 'imageData'; url: string }
+  | { type: 'file'; path: string };
 
 export interface ChatMessage {
   id: string;
@@ -25,10 +101,14 @@ export interface ChatMessage {
   content: string;
   timestamp: Date;
   isStreaming?: boolean;
+  items?: TurnItem[];
 }
 
+export type ReviewDecision = 'approved' | 'approved_for_session' | 'denied' | 'abort';
+
 export interface ApprovalRequest {
   id: string;
+  callId?: string;
   type: 'exec' | 'patch';
   command?: string;
   cwd?: string;
@@ -36,6 +116,34 @@ export interface ApprovalRequest {
   files?: string[];
 }
 
+export interface PendingApproval {
+  session_id: string;
+  approval_id: string;
+  call_id: string;
+  kind: 'exec' | 'patch';
+  request: EventMsg;
+  timestamp: number;
+}
+
+export interface AutoApprovalEvent {
+  session_id: string;
+  call_id: string;
+  command: string[];
+  cwd: string;
+  decision: ReviewDecision;
+}
+
+export interface SettingChange {
+  setting: string;
+  from: unknown;
+  to: unknown;
+}
+
+export interface SessionSettingsChangedEvent {
+  session_id: string;
+  changes: SettingChange[];
+}
+
 export interface ChatSession {
   id: string;
   name: string;
@@ -55,6 +163,14 @@ export interface CodexConfig {
   approvalPolicy: 'untrusted' | 'on-failure' | 'on-request' | 'never';
   sandboxMode: 'read-only' | 'workspace-write' | 'danger-full-access';
   codexPath?: string;
+  sessionSettings?: SessionSettings;
+}
+
+export interface SessionSettings {
+  reasoningEffort?: 'minimal' | 'low' | 'medium' | 'high';
+  userInstructions?: string;
+  writableRoots?: string[];
+  networkAccess?: boolean;
 }
 
 export const DEFAULT_CONFIG: CodexConfig = {
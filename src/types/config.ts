@@ -12,4 +12,36 @@ export interface Profile {
 export interface ProviderConfig {
   provider: ModelProvider;
   profile?: Profile;
-}
\ No newline at end of file
+}
+
+export interface ConnectionProfile {
+  host: string;
+  user: string;
+  port?: number;
+  key_path?: string;
+  jump_host?: string;
+  codex_path?: string;
+  auth: 'key' | 'agent' | 'password';
+}
+
+export type CommandPattern =
+  | { kind: 'prefix'; pattern: string[] }
+  | { kind: 'glob'; pattern: string }
+  | { kind: 'regex'; pattern: string };
+
+export interface ApprovalRule {
+  action: 'allow' | 'deny';
+  command: CommandPattern;
+  cwd?: string;
+}
+
+// USD per million tokens
+export interface ModelPrice {
+  input: number;
+  cached_input: number;
+  output: number;
+}
+
+export interface PriceTable {
+  models: Record<string, ModelPrice>;
+}